@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use geo_types::{Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+use geo_validity_check::Valid;
+
+/// A GeometryCollection of nested GeometryCollections, each containing
+/// MultiPolygons made of invalid (self-intersecting) Polygons.
+fn nested_collection(depth: usize, width: usize) -> GeometryCollection {
+    let bowtie = Polygon::new(
+        LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+        vec![],
+    );
+    let mut members: Vec<Geometry> = (0..width)
+        .map(|i| {
+            Geometry::MultiPolygon(MultiPolygon(vec![
+                bowtie.clone(),
+                Polygon::new(
+                    LineString::from(vec![
+                        (10. + i as f64, 0.),
+                        (11. + i as f64, 0.),
+                        (11. + i as f64, 1.),
+                        (10. + i as f64, 0.),
+                    ]),
+                    vec![],
+                ),
+            ]))
+        })
+        .collect();
+    if depth > 0 {
+        members.push(Geometry::GeometryCollection(nested_collection(
+            depth - 1,
+            width,
+        )));
+    }
+    GeometryCollection(members)
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let gc = nested_collection(10, 50);
+    c.bench_function("explain_invalidity nested GeometryCollection", |b| {
+        b.iter(|| black_box(&gc).explain_invalidity())
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
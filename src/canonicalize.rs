@@ -0,0 +1,270 @@
+use crate::{MaybeSync, ProblemReport, Valid};
+use geo::winding_order::Winding;
+use geo::{BoundingRect, GeoFloat, MapCoordsInPlace, RemoveRepeatedPoints};
+use geo_types::{Coord, CoordFloat, LineString, MultiPolygon, Polygon};
+use num_traits::FromPrimitive;
+use std::cmp::Ordering;
+
+/// A trait to compute a canonical form of a valid geometry, so that equivalent geometries
+/// can be compared for equality (or stored) regardless of how their coordinates were ordered.
+///
+/// The canonical form is defined as follows:
+/// - repeated consecutive coordinates are removed,
+/// - exterior rings are wound counter-clockwise and interior rings clockwise,
+/// - each ring starts at its lexicographically smallest vertex (by x, then by y),
+/// - interior rings are sorted by their first vertex,
+/// - the Polygons of a MultiPolygon are sorted by their bounding box.
+pub trait Canonicalize {
+    /// Return the canonical form of the geometry, or the reason(s) of invalidity
+    /// if the geometry is not valid.
+    fn canonicalize(&self) -> Result<Self, ProblemReport>
+    where
+        Self: Sized;
+}
+
+impl<T> Canonicalize for Polygon<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn canonicalize(&self) -> Result<Self, ProblemReport> {
+        if let Some(report) = self.explain_invalidity() {
+            return Err(report);
+        }
+        Ok(canonical_polygon(self))
+    }
+}
+
+impl<T> Canonicalize for MultiPolygon<T>
+where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    fn canonicalize(&self) -> Result<Self, ProblemReport> {
+        if let Some(report) = self.explain_invalidity() {
+            return Err(report);
+        }
+        let mut polygons: Vec<Polygon<T>> = self.0.iter().map(canonical_polygon).collect();
+        polygons.sort_by(|a, b| match (a.bounding_rect(), b.bounding_rect()) {
+            (Some(a), Some(b)) => {
+                cmp_coords(&a.min(), &b.min()).then(cmp_coords(&a.max(), &b.max()))
+            }
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        });
+        Ok(MultiPolygon(polygons))
+    }
+}
+
+/// Replace the negative zeros (-0.0) of the coordinates of a geometry by positive zeros,
+/// so that equal coordinates are also bitwise equal (see the `check_signed_zeros` option
+/// of the ValidationConfig).
+pub fn normalize_signed_zeros<T, G>(geom: &mut G)
+where
+    T: CoordFloat,
+    G: MapCoordsInPlace<T>,
+{
+    let positive = |v: T| if v.is_zero() { T::zero() } else { v };
+    geom.map_coords_in_place(|c| Coord {
+        x: positive(c.x),
+        y: positive(c.y),
+    });
+}
+
+/// Move the closing coordinate of the rings of a Polygon provided at their start
+/// (i.e. whose first coordinate is repeated, such as `[A, A, B, C]`) to their end,
+/// so that they are no longer reported as not closed (see `RingClosure`).
+pub fn normalize_ring_closure<T: CoordFloat>(polygon: &mut Polygon<T>) {
+    fn drop_leading_repeats<T: CoordFloat>(ring: &mut LineString<T>) {
+        while ring.0.len() > 2 && ring.0[0] == ring.0[1] {
+            ring.0.remove(0);
+        }
+    }
+    polygon.exterior_mut(drop_leading_repeats);
+    polygon.interiors_mut(|rings| rings.iter_mut().for_each(drop_leading_repeats));
+}
+
+fn canonical_polygon<T: GeoFloat + FromPrimitive>(polygon: &Polygon<T>) -> Polygon<T> {
+    let mut exterior = polygon.exterior().remove_repeated_points();
+    exterior.make_ccw_winding();
+    let mut interiors: Vec<LineString<T>> = polygon
+        .interiors()
+        .iter()
+        .map(|ring| {
+            let mut ring = ring.remove_repeated_points();
+            ring.make_cw_winding();
+            start_at_smallest_vertex(&ring)
+        })
+        .collect();
+    interiors.sort_by(|a, b| match (a.0.first(), b.0.first()) {
+        (Some(a), Some(b)) => cmp_coords(a, b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    });
+    Polygon::new(start_at_smallest_vertex(&exterior), interiors)
+}
+
+/// Rotate a closed ring so that it starts (and ends) at its lexicographically smallest vertex.
+fn start_at_smallest_vertex<T: GeoFloat>(ring: &LineString<T>) -> LineString<T> {
+    if ring.0.len() < 2 {
+        return ring.clone();
+    }
+    // Ignore the closing coordinate while rotating
+    let open = &ring.0[..ring.0.len() - 1];
+    let start = open
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| cmp_coords(a, b))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let mut coords: Vec<Coord<T>> = open[start..]
+        .iter()
+        .chain(&open[..start])
+        .copied()
+        .collect();
+    coords.push(open[start]);
+    LineString(coords)
+}
+
+fn cmp_coords<T: GeoFloat>(a: &Coord<T>, b: &Coord<T>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        normalize_ring_closure, normalize_signed_zeros, Canonicalize, CoordinatePosition, Problem,
+        ProblemAtPosition, ProblemPosition, ProblemReport, RingClosure, RingRole, Valid,
+        ValidationConfig,
+    };
+    use geo_types::{LineString, MultiPolygon, Polygon};
+
+    #[test]
+    fn test_canonicalize_polygon() {
+        // The same polygon, with its exterior ring wound clockwise or counter-clockwise,
+        // starting on different vertices, and with its interior rings in a different order
+        let p1 = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.), (1., 1.)]),
+                LineString::from(vec![(5., 5.), (5., 6.), (6., 6.), (6., 5.), (5., 5.)]),
+            ],
+        );
+        let p2 = Polygon::new(
+            LineString::from(vec![
+                (10., 10.),
+                (10., 0.),
+                (0., 0.),
+                (0., 10.),
+                (0., 10.),
+                (10., 10.),
+            ]),
+            vec![
+                LineString::from(vec![(6., 6.), (6., 5.), (5., 5.), (5., 6.), (6., 6.)]),
+                LineString::from(vec![(2., 1.), (2., 2.), (1., 2.), (1., 1.), (2., 1.)]),
+            ],
+        );
+        assert_ne!(p1, p2);
+
+        let c1 = p1.canonicalize().unwrap();
+        let c2 = p2.canonicalize().unwrap();
+        assert_eq!(c1, c2);
+        assert_eq!(
+            c1,
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+                vec![
+                    LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.), (1., 1.)]),
+                    LineString::from(vec![(5., 5.), (5., 6.), (6., 6.), (6., 5.), (5., 5.)]),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_multipolygon() {
+        let a = Polygon::new(
+            LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]),
+            vec![],
+        );
+        let b = Polygon::new(
+            LineString::from(vec![(5., 5.), (6., 5.), (6., 6.), (5., 6.), (5., 5.)]),
+            vec![],
+        );
+        let b_reversed = Polygon::new(
+            LineString::from(vec![(6., 6.), (6., 5.), (5., 5.), (5., 6.), (6., 6.)]),
+            vec![],
+        );
+        let mp1 = MultiPolygon(vec![a.clone(), b]);
+        let mp2 = MultiPolygon(vec![b_reversed, a]);
+        assert_eq!(mp1.canonicalize().unwrap(), mp2.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_canonicalize_invalid_polygon() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(
+            p.canonicalize(),
+            Err(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_normalize_signed_zeros() {
+        let config = ValidationConfig {
+            check_signed_zeros: true,
+            ..Default::default()
+        };
+        let mut ls: LineString = LineString::from(vec![(0., 0.), (1., -0.), (2., 1.)]);
+        assert!(ls.is_valid());
+        assert!(!ls.is_valid_with(&config));
+        assert_eq!(
+            ls.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SignedZero,
+                ProblemPosition::LineString(CoordinatePosition(Some(1)))
+            )]))
+        );
+
+        normalize_signed_zeros(&mut ls);
+        assert!(ls.0[1].y.is_sign_positive());
+        assert!(ls.is_valid_with(&config));
+    }
+
+    #[test]
+    fn test_ring_closed_at_start() {
+        // The closing coordinate was provided at the start of the ring
+        let mut p = Polygon::new(
+            LineString::from(vec![(0., 0.), (0., 0.), (4., 0.), (4., 4.), (0., 4.)]),
+            vec![],
+        );
+        let exact = ValidationConfig {
+            ring_closure: RingClosure::Exact,
+            ..Default::default()
+        };
+        // It is accepted by default (the first coordinate being only repeated)
+        assert!(p.is_valid());
+        assert!(!p.is_valid_with(&exact));
+        assert_eq!(
+            p.explain_invalidity_with(&exact),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::RingNotClosed,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0)))
+            )]))
+        );
+
+        normalize_ring_closure(&mut p);
+        assert!(p.is_valid_with(&exact));
+        assert_eq!(
+            p,
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+                vec![],
+            )
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    GeometryPosition, ProblemAtPosition, ProblemKind, ProblemPosition, ProblemReport,
+    ValidationConfig,
+};
+
+/// Where the geometry being checked lies in the geometry whose validity is reported,
+/// so that the position of the problems can be expressed relatively to the latter.
+#[derive(Clone, Copy)]
+pub(crate) enum PositionContext<'a> {
+    /// The geometry is the one whose validity is reported
+    Root,
+    /// The geometry is the n-th Polygon of a MultiPolygon
+    MultiPolygon(usize, &'a PositionContext<'a>),
+    /// The geometry is the n-th LineString of a MultiLineString
+    MultiLineString(usize, &'a PositionContext<'a>),
+    /// The geometry is the n-th geometry of a GeometryCollection
+    GeometryCollection(usize, &'a PositionContext<'a>),
+}
+
+impl PositionContext<'_> {
+    /// Express the position of a problem of the geometry being checked
+    /// relatively to the geometry whose validity is reported.
+    pub(crate) fn wrap(&self, position: ProblemPosition) -> ProblemPosition {
+        match self {
+            PositionContext::Root => position,
+            PositionContext::MultiPolygon(j, parent) => match position {
+                ProblemPosition::Polygon(ring_role, coord_pos) => parent.wrap(
+                    ProblemPosition::MultiPolygon(GeometryPosition(*j), ring_role, coord_pos),
+                ),
+                _ => unreachable!(),
+            },
+            PositionContext::MultiLineString(j, parent) => match position {
+                ProblemPosition::LineString(coord_pos) => parent.wrap(
+                    ProblemPosition::MultiLineString(GeometryPosition(*j), coord_pos),
+                ),
+                _ => unreachable!(),
+            },
+            PositionContext::GeometryCollection(i, parent) => parent.wrap(
+                ProblemPosition::GeometryCollection(GeometryPosition(*i), Box::new(position)),
+            ),
+        }
+    }
+}
+
+/// Collect the problems of a geometry into a single Vec shared with the geometries
+/// containing it (instead of allocating a Vec at each level of nesting).
+pub(crate) trait CollectProblems {
+    /// Push the problems of the geometry into `out`, their position being expressed
+    /// according to `ctx`.
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    );
+
+    /// Push the structural problems of the geometry into `out` (i.e. the problems found
+    /// without comparing rings or elements with each other, such as non-finite coordinates
+    /// or rings with too few points). This is the same as `collect_into` for the
+    /// geometries without relational checks.
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        self.collect_into(out, config, ctx)
+    }
+
+    /// Push the problems of the geometry into `out` as `collect_into` does, but possibly
+    /// stopping as soon as a first problem is pushed (the first problem pushed being
+    /// the same as with `collect_into`).
+    fn collect_first_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        self.collect_into(out, config, ctx)
+    }
+}
+
+/// Return the reason(s) of invalidity of the geometry, or None if valid.
+///
+/// With the `tracing` feature, an event is also emitted for each problem.
+pub(crate) fn explain<G: CollectProblems>(
+    geom: &G,
+    config: &ValidationConfig,
+) -> Option<ProblemReport> {
+    let mut reason = Vec::new();
+    geom.collect_into(&mut reason, config, PositionContext::Root);
+    #[cfg(feature = "tracing")]
+    for ProblemAtPosition(problem, position) in reason.iter() {
+        tracing::warn!(problem = problem.code(), position = ?position, "invalid geometry");
+    }
+    if reason.is_empty() {
+        None
+    } else {
+        Some(ProblemReport(reason))
+    }
+}
+
+/// Return the category of the most severe problem of the geometry, the relational checks
+/// being skipped when the structural checks find a more severe problem
+/// (see `Valid::primary_issue`).
+pub(crate) fn primary_issue<G: CollectProblems>(geom: &G) -> Option<ProblemKind> {
+    let config = ValidationConfig::default();
+    let mut reason = Vec::new();
+    geom.collect_structural_into(&mut reason, &config, PositionContext::Root);
+    let kind = reason.iter().map(|problem| problem.0.kind()).min();
+    if kind.is_some_and(|kind| kind < ProblemKind::SelfIntersection) {
+        return kind;
+    }
+    reason.clear();
+    geom.collect_into(&mut reason, &config, PositionContext::Root);
+    reason.iter().map(|problem| problem.0.kind()).min()
+}
+
+/// Return the first structural problem of the geometry if any, otherwise the first
+/// problem found by the full checks (see `Valid::quick_check`).
+pub(crate) fn quick_check<G: CollectProblems>(geom: &G) -> Result<(), ProblemAtPosition> {
+    let config = ValidationConfig::default();
+    let mut reason = Vec::new();
+    geom.collect_structural_into(&mut reason, &config, PositionContext::Root);
+    if reason.is_empty() {
+        geom.collect_first_into(&mut reason, &config, PositionContext::Root);
+    }
+    match reason.into_iter().next() {
+        None => Ok(()),
+        Some(problem) => Err(problem),
+    }
+}
+
+/// Return the first problem of the geometry, the checks being stopped as soon as
+/// it is found (see `Valid::first_problem`).
+pub(crate) fn first_problem<G: CollectProblems>(geom: &G) -> Option<ProblemAtPosition> {
+    let mut reason = Vec::new();
+    geom.collect_first_into(
+        &mut reason,
+        &ValidationConfig::default(),
+        PositionContext::Root,
+    );
+    reason.into_iter().next()
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::Valid;
+    use geo_types::{LineString, Polygon};
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    /// The name and the value of each field of an event
+    struct Fields(Vec<(String, String)>);
+
+    /// A subscriber keeping the level and the fields of the events it receives
+    #[derive(Default)]
+    struct Capture(Arc<Mutex<Vec<(Level, Fields)>>>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(vec![]);
+            event.record(&mut fields);
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), fields));
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_tracing_events() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        let capture = Capture::default();
+        let events = capture.0.clone();
+        let report = tracing::subscriber::with_default(capture, || p.explain_invalidity());
+        assert!(report.is_some());
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let (level, fields) = &events[0];
+        assert_eq!(*level, Level::WARN);
+        assert!(fields
+            .0
+            .contains(&("problem".to_string(), "\"self_intersection\"".to_string())));
+        assert!(fields.0.contains(&(
+            "position".to_string(),
+            "Polygon(Exterior, CoordinatePosition(Some(1)))".to_string()
+        )));
+    }
+}
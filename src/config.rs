@@ -0,0 +1,307 @@
+/// Options controlling the checks performed when validating a geometry
+/// (see the `is_valid_with` and `explain_invalidity_with` methods of the Valid trait).
+///
+/// The default configuration only performs the checks needed to follow
+/// the validity rules used by PostGIS (plus the finiteness of the coordinates),
+/// the other checks being opt-in:
+///
+/// ```rust
+/// use geo_validity_check::ValidationConfig;
+///
+/// let config = ValidationConfig {
+///     check_islands_in_holes: true,
+///     ..Default::default()
+/// };
+/// ```
+///
+/// A configuration can also be obtained from a `StrictnessProfile`, or with
+/// `ValidationConfig::ogc()` to follow the stricter rules of OGC Simple Features.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationConfig {
+    /// Report the Polygons of a MultiPolygon lying inside an interior ring of another
+    /// Polygon of the same MultiPolygon (an island in a lake).
+    /// This is valid but often unintended.
+    pub check_islands_in_holes: bool,
+    /// How the rings of Polygons that were not closed when they were built are handled.
+    pub ring_closure: RingClosure,
+    /// Report the Polygons (or the Polygons of MultiPolygons) of a GeometryCollection
+    /// whose area overlaps the area of a different member of the GeometryCollection
+    /// (which is often invalid for coverage data).
+    pub check_overlapping_collection_members: bool,
+    /// Three points are considered collinear when the absolute value of their orientation
+    /// determinant (twice the area of the triangle they form) is not greater than this value.
+    /// This is an absolute tolerance, to be chosen according to the magnitude of the
+    /// coordinates (the determinant grows with the square of the distances between the points).
+    /// With 0 (the default), the exact (robust) orientation test is used: it is free of
+    /// rounding errors whatever the magnitude of the coordinates, so no tolerance is needed
+    /// to avoid reporting points that are not collinear.
+    ///
+    /// This is used by the Triangle checks (Polygons don't check the collinearity of their
+    /// coordinates).
+    pub collinearity_eps: f64,
+    /// Two coordinates are considered identical when their distance is not greater than
+    /// this value (the default, 0, only considers strictly equal coordinates as identical).
+    pub near_duplicate_tolerance: f64,
+    /// Interior rings of Polygons are considered contained in the exterior ring when their
+    /// vertices outside the exterior ring are at most at this distance from it (default: 0).
+    pub containment_tolerance: f64,
+    /// Report the Polygons in which two interior rings and the exterior ring meet
+    /// at the same point, which can pinch (and disconnect) the interior of the Polygon.
+    pub check_triple_touch_points: bool,
+    /// The coordinates of Polygons (and MultiPolygons) whose absolute value is greater than
+    /// this value are reported, the relations between their rings (or elements) not being
+    /// computed as they could overflow (default: 1e150, whose square is close to `f64::MAX`).
+    /// For geometries with f32 coordinates, a lower limit (about 6.5e18) is always used.
+    pub max_coordinate_magnitude: f64,
+    /// Report the Polygons which are not convex (as required by some consumers, such as
+    /// collision meshes), i.e. which have interior rings or whose exterior ring doesn't
+    /// follow its convex hull.
+    pub check_convexity: bool,
+    /// Report the coordinates containing a negative zero (-0.0), which can cause equality
+    /// and hashing surprises (see `normalize_signed_zeros`).
+    pub check_signed_zeros: bool,
+    /// Report the interior rings of Polygons wound in the same direction as their exterior
+    /// ring, which many renderers draw as filled islands instead of holes.
+    pub check_holes_rendered_as_islands: bool,
+    /// The maximum number of elements (not counting the GeometryCollections themselves)
+    /// of the whole tree of nested GeometryCollections: larger GeometryCollections are
+    /// reported without validating their elements, which protects servers from
+    /// pathologically wide and deep collections (default: None, no limit).
+    pub max_collection_elements: Option<usize>,
+    /// Report the LineStrings of a MultiLineString sharing a collinear stretch with another
+    /// LineString of the same MultiLineString (which is often an error for network data).
+    pub check_overlapping_edges: bool,
+    /// Report the consecutive identical coordinates of LineStrings and Polygon rings
+    /// (at the second occurrence), which are valid but redundant.
+    pub check_repeated_points: bool,
+    /// The winding order the rings of Polygons must follow (default: None, any winding
+    /// order is accepted): the rings wound in the other direction are reported.
+    pub ring_orientation: Option<RingOrientation>,
+    /// Report the multi-geometries and GeometryCollections without any element
+    /// (which several formats handle specially), at `ProblemPosition::Whole`.
+    pub check_empty_geometries: bool,
+    /// Report the rings of Polygons whose first and last coordinates differ.
+    /// geo-types closes the rings when a Polygon is built with `Polygon::new`, but not
+    /// when it is obtained otherwise (e.g. deserialized with serde from a format which
+    /// doesn't close the rings), in which case such a ring is otherwise validated
+    /// as if it was closed.
+    pub check_unclosed_rings: bool,
+    /// Report the self-intersecting LineStrings (including the LineStrings of
+    /// MultiLineStrings), at the first coordinate of the first segment crossing another
+    /// segment. OGC Simple Features requires LineStrings to be simple, PostGIS doesn't.
+    pub check_linestring_simplicity: bool,
+    /// The rings of Polygons whose signed area, in absolute value, is not greater than this
+    /// value are reported as enclosing no area (`ZeroArea`). With 0 (the default), only the
+    /// rings whose vertices are all collinear (checked exactly) are reported.
+    pub zero_area_tolerance: f64,
+    /// Report the coordinates outside of the range of the geographic coordinates
+    /// (e.g. for data in EPSG:4326): a longitude (x) outside of [-180, 180]
+    /// or a latitude (y) outside of [-90, 90].
+    pub geographic: bool,
+    /// Report the segments of LineStrings and Polygon rings spanning more than 180°
+    /// of longitude (e.g. from 179° to -179°), which, for geographic coordinates, most
+    /// probably should have been split at the antimeridian.
+    pub check_antimeridian_crossings: bool,
+}
+
+impl ValidationConfig {
+    /// The validity rules used by PostGIS (`ST_IsValid`), plus the finiteness of the
+    /// coordinates: this is the default configuration, used by `is_valid` and
+    /// `explain_invalidity`.
+    pub fn postgis() -> Self {
+        ValidationConfig::default()
+    }
+
+    /// The stricter rules of OGC Simple Features: in addition to the PostGIS rules,
+    /// the exterior rings must be wound counter-clockwise and the interior rings clockwise,
+    /// the LineStrings must be simple and consecutive repeated points are not allowed.
+    pub fn ogc() -> Self {
+        ValidationConfig {
+            ring_orientation: Some(RingOrientation::ExteriorCounterClockwise),
+            check_repeated_points: true,
+            check_linestring_simplicity: true,
+            ..ValidationConfig::postgis()
+        }
+    }
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        ValidationConfig {
+            check_islands_in_holes: false,
+            ring_closure: RingClosure::default(),
+            check_overlapping_collection_members: false,
+            collinearity_eps: 0.,
+            near_duplicate_tolerance: 0.,
+            containment_tolerance: 0.,
+            check_triple_touch_points: false,
+            max_coordinate_magnitude: 1e150,
+            check_convexity: false,
+            check_signed_zeros: false,
+            check_holes_rendered_as_islands: false,
+            max_collection_elements: None,
+            check_overlapping_edges: false,
+            check_repeated_points: false,
+            ring_orientation: None,
+            check_empty_geometries: false,
+            check_unclosed_rings: false,
+            check_linestring_simplicity: false,
+            zero_area_tolerance: 0.,
+            geographic: false,
+            check_antimeridian_crossings: false,
+        }
+    }
+}
+
+/// Named sets of tolerances and optional checks, to avoid setting each of them:
+///
+/// ```rust
+/// use geo_validity_check::{StrictnessProfile, Valid};
+/// use geo_types::Triangle;
+///
+/// let t = Triangle::from([(0., 0.), (1., 0.), (2., 1e-10)]);
+/// assert!(t.is_valid_with(&StrictnessProfile::Lenient.config()));
+/// assert!(!t.is_valid_with(&StrictnessProfile::Strict.config()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictnessProfile {
+    /// Accept the small imprecisions commonly found in real-world data:
+    /// - `collinearity_eps`: 0 (exactly collinear points only),
+    /// - `near_duplicate_tolerance`: 0 (strictly equal coordinates only),
+    /// - `containment_tolerance`: 1e-9,
+    /// - `ring_closure`: `RingClosure::AutoClose`,
+    /// - `check_islands_in_holes`: false,
+    /// - `check_overlapping_collection_members`: false,
+    /// - `check_triple_touch_points`: false,
+    /// - `max_coordinate_magnitude`: 1e150,
+    /// - `check_convexity`: false,
+    /// - `check_signed_zeros`: false,
+    /// - `check_holes_rendered_as_islands`: false,
+    /// - `max_collection_elements`: None,
+    /// - `check_overlapping_edges`: false,
+    /// - `check_repeated_points`: false,
+    /// - `ring_orientation`: None,
+    /// - `check_empty_geometries`: false,
+    /// - `check_unclosed_rings`: false,
+    /// - `check_linestring_simplicity`: false,
+    /// - `zero_area_tolerance`: 0,
+    /// - `geographic`: false,
+    /// - `check_antimeridian_crossings`: false.
+    Lenient,
+    /// The default configuration, following the validity rules used by PostGIS:
+    /// - `collinearity_eps`: 0,
+    /// - `near_duplicate_tolerance`: 0,
+    /// - `containment_tolerance`: 0,
+    /// - `ring_closure`: `RingClosure::AutoClose`,
+    /// - `check_islands_in_holes`: false,
+    /// - `check_overlapping_collection_members`: false,
+    /// - `check_triple_touch_points`: false,
+    /// - `max_coordinate_magnitude`: 1e150,
+    /// - `check_convexity`: false,
+    /// - `check_signed_zeros`: false,
+    /// - `check_holes_rendered_as_islands`: false,
+    /// - `max_collection_elements`: None,
+    /// - `check_overlapping_edges`: false,
+    /// - `check_repeated_points`: false,
+    /// - `ring_orientation`: None,
+    /// - `check_empty_geometries`: false,
+    /// - `check_unclosed_rings`: false,
+    /// - `check_linestring_simplicity`: false,
+    /// - `zero_area_tolerance`: 0,
+    /// - `geographic`: false,
+    /// - `check_antimeridian_crossings`: false.
+    Standard,
+    /// Also report nearly degenerate geometries and the optional checks:
+    /// - `collinearity_eps`: 1e-9,
+    /// - `near_duplicate_tolerance`: 1e-9,
+    /// - `containment_tolerance`: 0,
+    /// - `ring_closure`: `RingClosure::Exact`,
+    /// - `check_islands_in_holes`: true,
+    /// - `check_overlapping_collection_members`: true,
+    /// - `check_triple_touch_points`: true,
+    /// - `max_coordinate_magnitude`: 1e150,
+    /// - `check_convexity`: false,
+    /// - `check_signed_zeros`: false,
+    /// - `check_holes_rendered_as_islands`: true,
+    /// - `max_collection_elements`: None,
+    /// - `check_overlapping_edges`: true,
+    /// - `check_repeated_points`: true,
+    /// - `ring_orientation`: None,
+    /// - `check_empty_geometries`: true,
+    /// - `check_unclosed_rings`: true,
+    /// - `check_linestring_simplicity`: false,
+    /// - `zero_area_tolerance`: 0,
+    /// - `geographic`: false,
+    /// - `check_antimeridian_crossings`: false.
+    Strict,
+}
+
+impl StrictnessProfile {
+    /// Return the ValidationConfig corresponding to this profile.
+    pub fn config(self) -> ValidationConfig {
+        match self {
+            StrictnessProfile::Lenient => ValidationConfig {
+                containment_tolerance: 1e-9,
+                ..Default::default()
+            },
+            StrictnessProfile::Standard => ValidationConfig::default(),
+            StrictnessProfile::Strict => ValidationConfig {
+                check_islands_in_holes: true,
+                ring_closure: RingClosure::Exact,
+                check_overlapping_collection_members: true,
+                collinearity_eps: 1e-9,
+                near_duplicate_tolerance: 1e-9,
+                containment_tolerance: 0.,
+                check_triple_touch_points: true,
+                check_holes_rendered_as_islands: true,
+                check_overlapping_edges: true,
+                check_repeated_points: true,
+                check_empty_geometries: true,
+                check_unclosed_rings: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<StrictnessProfile> for ValidationConfig {
+    fn from(profile: StrictnessProfile) -> Self {
+        profile.config()
+    }
+}
+
+/// How to handle the rings of Polygons that were not closed when they were built.
+///
+/// geo-types closes the rings of a Polygon when it is built, by adding a copy
+/// of the first coordinate at the end of the ring if needed. A nearly-closed ring
+/// (i.e. whose last coordinate is very close to the first one, which often denotes a precision
+/// issue in the input data) thus ends up with a tiny closing segment.
+/// Such a closing segment is detected when its length is not greater than 1e-6 times
+/// the diagonal of the bounding box of the ring.
+///
+/// Unless `AutoClose` is used, `RingNotClosed` is also reported for the rings whose closing
+/// coordinate was provided at their start instead of their end, their first coordinate
+/// being repeated (see `normalize_ring_closure`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum RingClosure {
+    /// Report `RingNotClosed` for any nearly-closed ring.
+    Exact,
+    /// Accept nearly-closed rings whose first and last coordinates are
+    /// at most at the given distance, report `RingNotClosed` for the others.
+    Snap(f64),
+    /// Accept nearly-closed rings, as closed by geo-types (this is the default).
+    #[default]
+    AutoClose,
+}
+
+/// The winding order convention followed by the rings of Polygons
+/// (see `ValidationConfig::ring_orientation`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingOrientation {
+    /// The exterior ring is wound counter-clockwise and the interior rings clockwise
+    /// (as required by OGC Simple Features and by GeoJSON).
+    ExteriorCounterClockwise,
+    /// The exterior ring is wound clockwise and the interior rings counter-clockwise
+    /// (as in shapefiles).
+    ExteriorClockwise,
+}
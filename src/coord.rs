@@ -1,4 +1,7 @@
-use crate::{utils, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid};
+use crate::collect::{self, CollectProblems, PositionContext};
+use crate::{
+    utils, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid, ValidationConfig,
+};
 use geo::GeoFloat;
 use geo_types::Coord;
 
@@ -6,27 +9,49 @@ impl<T> Valid for Coord<T>
 where
     T: GeoFloat,
 {
-    fn is_valid(&self) -> bool {
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
         if utils::check_coord_is_not_finite(self) {
             return false;
         }
+        if utils::check_coord_has_negative_zero(self, config)
+            || utils::check_coord_out_of_geographic_bounds(self, config)
+        {
+            return false;
+        }
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
 
+impl<T> CollectProblems for Coord<T>
+where
+    T: GeoFloat,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
         if utils::check_coord_is_not_finite(self) {
-            reason.push(ProblemAtPosition(
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Point,
+                ctx.wrap(ProblemPosition::Point),
             ));
         }
-
-        // Return the reason(s) of invalidity, or None if valid
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
+        if utils::check_coord_has_negative_zero(self, config) {
+            out.push(ProblemAtPosition(
+                Problem::SignedZero,
+                ctx.wrap(ProblemPosition::Point),
+            ));
+        }
+        if utils::check_coord_out_of_geographic_bounds(self, config) {
+            out.push(ProblemAtPosition(
+                Problem::OutOfGeographicBounds,
+                ctx.wrap(ProblemPosition::Point),
+            ));
         }
     }
 }
@@ -0,0 +1,284 @@
+use crate::collect::{CollectProblems, PositionContext};
+use crate::{
+    CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition, RingRole,
+    ValidationConfig,
+};
+use geo::{Area, BooleanOps, BoundingRect, EuclideanDistance, EuclideanLength};
+use geo_types::{Coord, MultiPolygon, Polygon, Rect};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+
+/// Check that a set of Polygons forms a clean planar coverage (as parcels datasets should):
+/// adjacent Polygons must share their boundaries exactly, without gaps or overlaps.
+///
+/// The following problems are reported (the position of each Polygon being expressed as if
+/// the slice was a MultiPolygon):
+/// - `ElementsOverlaps` for each Polygon of a pair of Polygons whose intersection has a non-zero area
+///   (along with the lowest index of the other Polygons it overlaps),
+/// - `CoverageGap` for each Polygon of a pair of Polygons which don't touch but are closer
+///   than `tolerance`,
+/// - `CoverageGap` for each Polygon bordering a sliver (a hole in the union of the Polygons)
+///   narrower than `tolerance`.
+///
+/// The Polygons which are not valid (according to the default `ValidationConfig`) are reported
+/// with their own problems and are otherwise ignored.
+pub fn validate_planar_coverage(polys: &[Polygon], tolerance: f64) -> Vec<ProblemAtPosition> {
+    // The problems of each invalid Polygon (the boolean operations can't be used on them)
+    let invalid: Vec<Vec<ProblemAtPosition>> = polys
+        .iter()
+        .enumerate()
+        .map(|(j, polygon)| {
+            let mut problems = Vec::new();
+            polygon.collect_into(
+                &mut problems,
+                &ValidationConfig::default(),
+                PositionContext::MultiPolygon(j, &PositionContext::Root),
+            );
+            problems
+        })
+        .collect();
+    // The first Polygon found to overlap each Polygon
+    let mut overlaps: Vec<Option<usize>> = vec![None; polys.len()];
+    let mut gaps = vec![false; polys.len()];
+
+    // Only the Polygons whose bounding boxes are closer than `tolerance` can overlap
+    // or leave a gap between them
+    let tree = RTree::bulk_load(
+        polys
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| invalid[*j].is_empty())
+            .filter_map(|(j, polygon)| {
+                let rect = polygon.bounding_rect()?;
+                let delta = Coord {
+                    x: tolerance,
+                    y: tolerance,
+                };
+                Some(GeomWithData::new(
+                    Rectangle::from_corners((rect.min() - delta).x_y(), (rect.max() + delta).x_y()),
+                    j,
+                ))
+            })
+            .collect(),
+    );
+    let candidates = |rect: Rect| {
+        let mut found: Vec<usize> = tree
+            .locate_in_envelope_intersecting(&AABB::from_corners(
+                rect.min().x_y(),
+                rect.max().x_y(),
+            ))
+            .map(|candidate| candidate.data)
+            .collect();
+        found.sort_unstable();
+        found
+    };
+
+    for (j, polygon) in polys.iter().enumerate() {
+        if !invalid[j].is_empty() {
+            continue;
+        }
+        let neighbours = polygon.bounding_rect().map(candidates).unwrap_or_default();
+        for i in neighbours.into_iter().filter(|i| *i > j) {
+            let pol2 = &polys[i];
+            if polygon.intersection(pol2).unsigned_area() > 0. {
+                overlaps[j].get_or_insert(i);
+                overlaps[i].get_or_insert(j);
+                continue;
+            }
+            let distance = polygon.euclidean_distance(pol2);
+            if distance > 0. && distance <= tolerance {
+                gaps[j] = true;
+                gaps[i] = true;
+            }
+        }
+    }
+
+    // Gaps that are enclosed by the coverage end up as holes in the union of the polygons
+    // (merged pairwise, so that each union only involves Polygons of similar sizes)
+    let mut parts: Vec<MultiPolygon> = polys
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| invalid[*j].is_empty())
+        .map(|(_, polygon)| MultiPolygon(vec![polygon.clone()]))
+        .collect();
+    while parts.len() > 1 {
+        parts = parts
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => a.union(b),
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+    let union = parts.pop().unwrap_or(MultiPolygon(vec![]));
+    for hole in union.iter().flat_map(|polygon| polygon.interiors()) {
+        let sliver = Polygon::new(hole.clone(), vec![]);
+        // Approximate the width of the hole (this is exact for a long and thin rectangle)
+        let width = 2. * sliver.unsigned_area() / hole.euclidean_length();
+        if width > tolerance {
+            continue;
+        }
+        let neighbours = sliver.bounding_rect().map(candidates).unwrap_or_default();
+        for j in neighbours {
+            if polys[j].euclidean_distance(&sliver) <= tolerance {
+                gaps[j] = true;
+            }
+        }
+    }
+
+    let mut reason = Vec::new();
+    for (j, problems) in invalid.into_iter().enumerate() {
+        reason.extend(problems);
+        let overlap = overlaps[j].map(|other| Problem::ElementsOverlaps { other });
+        let gap = gaps[j].then_some(Problem::CoverageGap);
+        for problem in [overlap, gap].into_iter().flatten() {
+            reason.push(ProblemAtPosition(
+                problem,
+                ProblemPosition::MultiPolygon(
+                    GeometryPosition(j),
+                    RingRole::Exterior,
+                    CoordinatePosition(None),
+                ),
+            ));
+        }
+    }
+    reason
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_planar_coverage;
+    use crate::{
+        CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition, RingRole,
+    };
+    use geo_types::{LineString, Polygon};
+
+    fn square(x0: f64, y0: f64, x1: f64, y1: f64) -> Polygon {
+        Polygon::new(
+            LineString::from(vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1), (x0, y0)]),
+            vec![],
+        )
+    }
+
+    fn at(problem: Problem, j: usize) -> ProblemAtPosition {
+        ProblemAtPosition(
+            problem,
+            ProblemPosition::MultiPolygon(
+                GeometryPosition(j),
+                RingRole::Exterior,
+                CoordinatePosition(None),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_coverage_valid() {
+        let polys = vec![
+            square(0., 0., 1., 1.),
+            square(1., 0., 2., 1.),
+            square(0., 1., 2., 2.),
+        ];
+        assert!(validate_planar_coverage(&polys, 0.01).is_empty());
+    }
+
+    #[test]
+    fn test_coverage_overlap() {
+        let polys = vec![
+            square(0., 0., 1., 1.),
+            square(0.9, 0., 2., 1.),
+            square(5., 5., 6., 6.),
+        ];
+        assert_eq!(
+            validate_planar_coverage(&polys, 0.01),
+            vec![
+                at(Problem::ElementsOverlaps { other: 1 }, 0),
+                at(Problem::ElementsOverlaps { other: 0 }, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_gap() {
+        // The two first squares are separated by a 0.001 wide gap
+        let polys = vec![
+            square(0., 0., 1., 1.),
+            square(1.001, 0., 2., 1.),
+            square(5., 5., 6., 6.),
+        ];
+        assert_eq!(
+            validate_planar_coverage(&polys, 0.01),
+            vec![at(Problem::CoverageGap, 0), at(Problem::CoverageGap, 1)]
+        );
+
+        // The same gap is accepted with a smaller tolerance
+        assert!(validate_planar_coverage(&polys, 0.0001).is_empty());
+    }
+
+    #[test]
+    fn test_coverage_invalid_polygon() {
+        // The invalid Polygon is reported but not compared with the others
+        let polys = vec![
+            square(0., 0., 1., 1.),
+            square(1.001, 0., 2., 1.),
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (f64::NAN, 0.), (1., 1.), (0., 0.)]),
+                vec![],
+            ),
+        ];
+        assert_eq!(
+            validate_planar_coverage(&polys, 0.01),
+            vec![
+                at(Problem::CoverageGap, 0),
+                at(Problem::CoverageGap, 1),
+                ProblemAtPosition(
+                    Problem::NotFinite,
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(2),
+                        RingRole::Exterior,
+                        CoordinatePosition(Some(1)),
+                    ),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_grid() {
+        // A 20x20 grid of disjoint squares, the two last squares being too close
+        let mut polys: Vec<Polygon> = (0..400)
+            .map(|k| {
+                let (x, y) = ((k % 20) as f64 * 2., (k / 20) as f64 * 2.);
+                square(x, y, x + 1., y + 1.)
+            })
+            .collect();
+        polys[399] = square(38. - 0.995, 38., 39., 39.);
+        assert_eq!(
+            validate_planar_coverage(&polys, 0.01),
+            vec![at(Problem::CoverageGap, 398), at(Problem::CoverageGap, 399)]
+        );
+    }
+
+    #[test]
+    fn test_coverage_sliver() {
+        // The two lower squares touch the upper rectangle but are separated
+        // from each other by a 0.001 wide sliver
+        let polys = vec![
+            square(0., 0., 1., 1.),
+            square(1.001, 0., 2., 1.),
+            Polygon::new(
+                LineString::from(vec![(0., 1.), (2., 1.), (2., 2.), (0., 2.), (0., 1.)]),
+                vec![],
+            ),
+            square(0., -1., 2., 0.),
+        ];
+        assert_eq!(
+            validate_planar_coverage(&polys, 0.01),
+            vec![
+                at(Problem::CoverageGap, 0),
+                at(Problem::CoverageGap, 1),
+                at(Problem::CoverageGap, 2),
+                at(Problem::CoverageGap, 3)
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{ProblemAtPosition, ProblemFlags, ProblemKind, ProblemReport, Valid, ValidationConfig};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// A Cow (borrowed or owned geometry) is valid if the geometry it holds is valid.
+impl<'a, T> Valid for Cow<'a, T>
+where
+    T: Valid + ToOwned + ?Sized,
+{
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        self.as_ref().is_valid_with(config)
+    }
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        self.as_ref().explain_invalidity_with(config)
+    }
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        self.as_ref().quick_check()
+    }
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        self.as_ref().primary_issue()
+    }
+    fn first_problem(&self) -> Option<ProblemAtPosition> {
+        self.as_ref().first_problem()
+    }
+    fn problem_flags(&self) -> ProblemFlags {
+        self.as_ref().problem_flags()
+    }
+    fn problem_counts(&self) -> BTreeMap<&'static str, usize> {
+        self.as_ref().problem_counts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CoordinatePosition, Problem, ProblemAtPosition, ProblemFlags, ProblemPosition,
+        ProblemReport, RingRole, Valid,
+    };
+    use geo_types::{Geometry, LineString, Polygon};
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_cow_borrowed_polygon() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        let cow: Cow<Polygon> = Cow::Borrowed(&polygon);
+        assert!(!cow.is_valid());
+        assert_eq!(
+            cow.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+            )]))
+        );
+        assert_eq!(cow.first_problem(), polygon.first_problem());
+        assert_eq!(cow.problem_flags(), ProblemFlags::SELF_INTERSECTION);
+        assert_eq!(
+            cow.problem_counts(),
+            BTreeMap::from([("self_intersection", 1)])
+        );
+    }
+
+    #[test]
+    fn test_cow_owned_geometry() {
+        let cow: Cow<Geometry> = Cow::Owned(Geometry::Polygon(Polygon::new(
+            LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]),
+            vec![],
+        )));
+        assert!(cow.is_valid());
+        assert!(cow.explain_invalidity().is_none());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Problem, ProblemAtPosition, Valid};
+use geo_types::Geometry;
+
+/// Check the validity of a geometry resulting from an operation (`after`), comparing
+/// it with the geometry the operation was applied to (`before`).
+///
+/// All the problems of `after` are returned. The non-finite coordinates and the
+/// self-intersections which were not found at the same position in `before` are attributed
+/// to the operation: they are reported as `IntroducedNotFinite` and
+/// `IntroducedSelfIntersection` (instead of `NotFinite` and `SelfIntersection`).
+pub fn validate_diff(before: &Geometry, after: &Geometry) -> Vec<ProblemAtPosition> {
+    let after_problems = match after.explain_invalidity() {
+        None => return vec![],
+        Some(report) => report.0,
+    };
+    let before_problems = before
+        .explain_invalidity()
+        .map(|report| report.0)
+        .unwrap_or_default();
+
+    after_problems
+        .into_iter()
+        .map(|problem| {
+            if before_problems.contains(&problem) {
+                return problem;
+            }
+            match problem {
+                ProblemAtPosition(Problem::NotFinite, position) => {
+                    ProblemAtPosition(Problem::IntroducedNotFinite, position)
+                }
+                ProblemAtPosition(Problem::SelfIntersection, position) => {
+                    ProblemAtPosition(Problem::IntroducedSelfIntersection, position)
+                }
+                problem => problem,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        validate_diff, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, RingRole,
+    };
+    use geo_types::{Geometry, LineString, Polygon};
+
+    #[test]
+    fn test_validate_diff_introduced_nan() {
+        let before = Geometry::LineString(LineString::from(vec![(0., 0.), (1., 1.), (2., 0.)]));
+        // An operation divided by zero
+        let after =
+            Geometry::LineString(LineString::from(vec![(0., 0.), (f64::NAN, 1.), (2., 0.)]));
+        assert_eq!(
+            validate_diff(&before, &after),
+            vec![ProblemAtPosition(
+                Problem::IntroducedNotFinite,
+                ProblemPosition::LineString(CoordinatePosition(Some(1)))
+            )]
+        );
+
+        // The problem was already there before the operation
+        assert_eq!(
+            validate_diff(&after, &after),
+            vec![ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::LineString(CoordinatePosition(Some(1)))
+            )]
+        );
+        assert!(validate_diff(&after, &before).is_empty());
+    }
+
+    #[test]
+    fn test_validate_diff_introduced_self_intersection() {
+        let before = Geometry::Polygon(Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 2.), (0., 2.), (0., 0.)]),
+            vec![],
+        ));
+        // An operation swapped two vertices
+        let after = Geometry::Polygon(Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        ));
+        assert_eq!(
+            validate_diff(&before, &after),
+            vec![ProblemAtPosition(
+                Problem::IntroducedSelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+            )]
+        );
+    }
+}
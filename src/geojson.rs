@@ -0,0 +1,106 @@
+use crate::{ProblemReport, Valid};
+use geo_types::Geometry;
+
+/// Check the validity of the geometry of each feature of a GeoJSON FeatureCollection,
+/// returning the index and the ProblemReport of each invalid feature (an empty Vec
+/// meaning that all the features are valid).
+///
+/// The features without geometry (`"geometry": null`) and the geometries that can't
+/// be converted to geo-types geometries are skipped.
+///
+/// ```rust
+/// use geo_validity_check::validate_feature_collection;
+///
+/// let fc: geojson::FeatureCollection = r#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [0, 0]}},
+///         {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[0, 0]]}}
+///     ]
+/// }"#.parse().unwrap();
+/// let invalid = validate_feature_collection(&fc);
+/// assert_eq!(invalid.len(), 1);
+/// assert_eq!(invalid[0].0, 1);
+/// ```
+pub fn validate_feature_collection(
+    fc: &::geojson::FeatureCollection,
+) -> Vec<(usize, ProblemReport)> {
+    fc.features
+        .iter()
+        .enumerate()
+        .filter_map(|(i, feature)| {
+            let geometry = Geometry::<f64>::try_from(&feature.geometry.as_ref()?.value).ok()?;
+            Some((i, geometry.explain_invalidity()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        validate_feature_collection, CoordinatePosition, GeometryPosition, Problem,
+        ProblemAtPosition, ProblemPosition, ProblemReport, RingRole,
+    };
+
+    #[test]
+    fn test_validate_feature_collection() {
+        let fc: ::geojson::FeatureCollection = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature", "properties": {},
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [[[0, 0], [2, 0], [2, 2], [0, 2], [0, 0]]]
+                    }
+                },
+                {
+                    "type": "Feature", "properties": {},
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [[[0, 0], [2, 2], [2, 0], [0, 2], [0, 0]]]
+                    }
+                },
+                {"type": "Feature", "properties": {}, "geometry": null},
+                {
+                    "type": "Feature", "properties": {},
+                    "geometry": {
+                        "type": "GeometryCollection",
+                        "geometries": [
+                            {"type": "Point", "coordinates": [1, 1]},
+                            {"type": "LineString", "coordinates": [[0, 0], [0, 0]]}
+                        ]
+                    }
+                },
+                {
+                    "type": "Feature", "properties": {},
+                    "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}
+                }
+            ]
+        }"#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            validate_feature_collection(&fc),
+            vec![
+                (
+                    1,
+                    ProblemReport(vec![ProblemAtPosition(
+                        Problem::SelfIntersection,
+                        ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0)))
+                    )])
+                ),
+                (
+                    3,
+                    ProblemReport(vec![ProblemAtPosition(
+                        Problem::TooFewPoints,
+                        ProblemPosition::GeometryCollection(
+                            GeometryPosition(1),
+                            Box::new(ProblemPosition::LineString(CoordinatePosition(Some(0))))
+                        )
+                    )])
+                ),
+            ]
+        );
+    }
+}
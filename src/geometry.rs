@@ -1,33 +1,186 @@
-use crate::{ProblemReport, Valid};
+use crate::collect::{self, CollectProblems, PositionContext};
+use crate::{MaybeSync, ProblemAtPosition, ProblemKind, ProblemReport, Valid, ValidationConfig};
+use geo::GeoFloat;
 use geo_types::Geometry;
+use num_traits::FromPrimitive;
 
-impl Valid for Geometry {
-    fn is_valid(&self) -> bool {
+impl<T> Valid for Geometry<T>
+where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
         match self {
-            Geometry::Point(e) => e.is_valid(),
-            Geometry::Line(e) => e.is_valid(),
-            Geometry::Rect(e) => e.is_valid(),
-            Geometry::Triangle(e) => e.is_valid(),
-            Geometry::LineString(e) => e.is_valid(),
-            Geometry::Polygon(e) => e.is_valid(),
-            Geometry::MultiPoint(e) => e.is_valid(),
-            Geometry::MultiLineString(e) => e.is_valid(),
-            Geometry::MultiPolygon(e) => e.is_valid(),
-            Geometry::GeometryCollection(e) => e.is_valid(),
+            Geometry::Point(e) => e.is_valid_with(config),
+            Geometry::Line(e) => e.is_valid_with(config),
+            Geometry::Rect(e) => e.is_valid_with(config),
+            Geometry::Triangle(e) => e.is_valid_with(config),
+            Geometry::LineString(e) => e.is_valid_with(config),
+            Geometry::Polygon(e) => e.is_valid_with(config),
+            Geometry::MultiPoint(e) => e.is_valid_with(config),
+            Geometry::MultiLineString(e) => e.is_valid_with(config),
+            Geometry::MultiPolygon(e) => e.is_valid_with(config),
+            Geometry::GeometryCollection(e) => e.is_valid_with(config),
         }
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        collect::quick_check(self)
+    }
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        collect::primary_issue(self)
+    }
+    fn first_problem(&self) -> Option<ProblemAtPosition> {
+        collect::first_problem(self)
+    }
+}
+
+impl<T> CollectProblems for Geometry<T>
+where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        match self {
+            Geometry::Point(e) => e.collect_into(out, config, ctx),
+            Geometry::Line(e) => e.collect_into(out, config, ctx),
+            Geometry::Rect(e) => e.collect_into(out, config, ctx),
+            Geometry::Triangle(e) => e.collect_into(out, config, ctx),
+            Geometry::LineString(e) => e.collect_into(out, config, ctx),
+            Geometry::Polygon(e) => e.collect_into(out, config, ctx),
+            Geometry::MultiPoint(e) => e.collect_into(out, config, ctx),
+            Geometry::MultiLineString(e) => e.collect_into(out, config, ctx),
+            Geometry::MultiPolygon(e) => e.collect_into(out, config, ctx),
+            Geometry::GeometryCollection(e) => e.collect_into(out, config, ctx),
+        }
+    }
+
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        match self {
+            Geometry::Point(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::Line(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::Rect(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::Triangle(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::LineString(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::Polygon(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::MultiPoint(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::MultiLineString(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::MultiPolygon(e) => e.collect_structural_into(out, config, ctx),
+            Geometry::GeometryCollection(e) => e.collect_structural_into(out, config, ctx),
+        }
+    }
+
+    fn collect_first_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
         match self {
-            Geometry::Point(e) => e.explain_invalidity(),
-            Geometry::Line(e) => e.explain_invalidity(),
-            Geometry::Rect(e) => e.explain_invalidity(),
-            Geometry::Triangle(e) => e.explain_invalidity(),
-            Geometry::LineString(e) => e.explain_invalidity(),
-            Geometry::Polygon(e) => e.explain_invalidity(),
-            Geometry::MultiPoint(e) => e.explain_invalidity(),
-            Geometry::MultiLineString(e) => e.explain_invalidity(),
-            Geometry::MultiPolygon(e) => e.explain_invalidity(),
-            Geometry::GeometryCollection(e) => e.explain_invalidity(),
+            Geometry::Point(e) => e.collect_first_into(out, config, ctx),
+            Geometry::Line(e) => e.collect_first_into(out, config, ctx),
+            Geometry::Rect(e) => e.collect_first_into(out, config, ctx),
+            Geometry::Triangle(e) => e.collect_first_into(out, config, ctx),
+            Geometry::LineString(e) => e.collect_first_into(out, config, ctx),
+            Geometry::Polygon(e) => e.collect_first_into(out, config, ctx),
+            Geometry::MultiPoint(e) => e.collect_first_into(out, config, ctx),
+            Geometry::MultiLineString(e) => e.collect_first_into(out, config, ctx),
+            Geometry::MultiPolygon(e) => e.collect_first_into(out, config, ctx),
+            Geometry::GeometryCollection(e) => e.collect_first_into(out, config, ctx),
         }
     }
 }
+
+/// Return the reason(s) of invalidity of a geometry, or None if valid: a single entry point
+/// for geometries with f32 or f64 coordinates (the positions of the problems don't depend
+/// on the type of the coordinates).
+pub fn validate<T: GeoFloat + FromPrimitive + MaybeSync>(
+    g: &Geometry<T>,
+) -> Option<Vec<ProblemAtPosition>> {
+    g.explain_invalidity().map(|report| report.0)
+}
+
+/// Validate each geometry of a slice, returning the reason(s) of invalidity of the invalid
+/// ones along with their index in the slice (the valid geometries are omitted).
+pub fn validate_all<T: GeoFloat + FromPrimitive + MaybeSync>(
+    geoms: &[Geometry<T>],
+) -> Vec<(usize, ProblemReport)> {
+    geoms
+        .iter()
+        .enumerate()
+        .filter_map(|(i, g)| Some((i, g.explain_invalidity()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        validate, validate_all, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition,
+        ProblemReport, RingRole,
+    };
+    use geo_types::{Geometry, LineString, Point, Polygon};
+
+    #[test]
+    fn test_validate_f32_and_f64() {
+        let expected = Some(vec![ProblemAtPosition(
+            Problem::SelfIntersection,
+            ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1))),
+        )]);
+
+        let p: Polygon<f32> = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(validate(&Geometry::Polygon(p)), expected);
+        assert_eq!(validate(&Geometry::Point(Point::new(1f32, 2.))), None);
+
+        let p: Polygon<f64> = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(validate(&Geometry::Polygon(p)), expected);
+        assert_eq!(validate(&Geometry::Point(Point::new(1f64, 2.))), None);
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let geoms = vec![
+            Geometry::Point(Point::new(1., 2.)),
+            Geometry::Polygon(Polygon::new(
+                LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+                vec![],
+            )),
+            Geometry::Point(Point::new(f64::NAN, 2.)),
+        ];
+        assert_eq!(
+            validate_all(&geoms),
+            vec![
+                (
+                    1,
+                    ProblemReport(vec![ProblemAtPosition(
+                        Problem::SelfIntersection,
+                        ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1))),
+                    )])
+                ),
+                (
+                    2,
+                    ProblemReport(vec![ProblemAtPosition(
+                        Problem::NotFinite,
+                        ProblemPosition::Point,
+                    )])
+                ),
+            ]
+        );
+        assert!(validate_all(&geoms[..1]).is_empty());
+    }
+}
@@ -1,53 +1,345 @@
-use crate::{GeometryPosition, ProblemAtPosition, ProblemPosition, ProblemReport, Valid};
-use geo_types::GeometryCollection;
+use crate::collect::{self, CollectProblems, PositionContext};
+use crate::profile::{self, Check};
+use crate::{
+    CoordinatePosition, GeometryPosition, MaybeSync, Problem, ProblemAtPosition, ProblemKind,
+    ProblemPosition, ProblemReport, RingRole, Valid, ValidationConfig,
+};
+use geo::coordinate_position::CoordPos;
+use geo::dimensions::Dimensions;
+use geo::{BoundingRect, CoordsIter, GeoFloat, Relate};
+use geo_types::{Geometry, GeometryCollection, Polygon};
+use num_traits::FromPrimitive;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+use std::collections::BTreeMap;
 
 /// GeometryCollection is valid if all its elements are valid
-impl Valid for GeometryCollection {
-    fn is_valid(&self) -> bool {
-        for geometry in self.0.iter() {
-            if !geometry.is_valid() {
+///
+/// Optionally (see `ValidationConfig::check_overlapping_collection_members`),
+/// the areas of different members overlapping each other are reported.
+///
+/// When the number of elements of the whole tree of nested GeometryCollections exceeds
+/// `ValidationConfig::max_collection_elements`, only `CollectionTooLarge` is reported
+/// (the elements are not validated).
+impl<T> Valid for GeometryCollection<T>
+where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        if let Some(max) = config.max_collection_elements {
+            if element_beyond(self, max, &mut 0).is_some() {
                 return false;
             }
+            // The nested GeometryCollections have fewer elements than this one
+            return self.is_valid_with(&without_element_cap(config));
+        }
+        if config.check_empty_geometries && self.0.is_empty() {
+            return false;
+        }
+        #[cfg(feature = "parallel")]
+        let all_valid = {
+            use rayon::prelude::*;
+            self.0
+                .par_iter()
+                .all(|geometry| geometry.is_valid_with(config))
+        };
+        #[cfg(not(feature = "parallel"))]
+        let all_valid = self.0.iter().all(|geometry| geometry.is_valid_with(config));
+        if !all_valid {
+            return false;
+        }
+        if config.check_overlapping_collection_members && !overlapping_members(self).is_empty() {
+            return false;
         }
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        collect::quick_check(self)
+    }
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        collect::primary_issue(self)
+    }
+}
 
-        // Loop over all the geometries, collect the reasons of invalidity
-        // and change the ProblemPosition to reflect the GeometryCollection
+impl<T> CollectProblems for GeometryCollection<T>
+where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if let Some(max) = config.max_collection_elements {
+            match element_beyond(self, max, &mut 0) {
+                Some(position) => out.push(ProblemAtPosition(
+                    Problem::CollectionTooLarge,
+                    ctx.wrap(position),
+                )),
+                None => self.collect_into(out, &without_element_cap(config), ctx),
+            }
+            return;
+        }
+
+        if config.check_empty_geometries && self.0.is_empty() {
+            out.push(ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ctx.wrap(ProblemPosition::Whole),
+            ));
+        }
+        // Loop over all the geometries and collect the reasons of invalidity
+        // (their ProblemPosition being changed to reflect the GeometryCollection),
+        // concurrently with the `parallel` feature, in the order of the geometries
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            let problems: Vec<Vec<ProblemAtPosition>> = self
+                .0
+                .par_iter()
+                .enumerate()
+                .map(|(i, geometry)| {
+                    let mut geometry_out = Vec::new();
+                    geometry.collect_into(
+                        &mut geometry_out,
+                        config,
+                        PositionContext::GeometryCollection(i, &ctx),
+                    );
+                    geometry_out
+                })
+                .collect();
+            out.extend(problems.into_iter().flatten());
+        }
+        #[cfg(not(feature = "parallel"))]
         for (i, geometry) in self.0.iter().enumerate() {
-            let temp_reason = geometry.explain_invalidity();
-            if let Some(temp_reason) = temp_reason {
-                for ProblemAtPosition(problem, position) in temp_reason.0 {
-                    reason.push(ProblemAtPosition(
-                        problem,
-                        ProblemPosition::GeometryCollection(
-                            GeometryPosition(i),
-                            Box::new(position),
-                        ),
-                    ));
-                }
+            geometry.collect_into(out, config, PositionContext::GeometryCollection(i, &ctx));
+        }
+
+        if config.check_overlapping_collection_members {
+            for ((i, part), other) in overlapping_members(self) {
+                let position = match part {
+                    None => ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None)),
+                    Some(k) => ProblemPosition::MultiPolygon(
+                        GeometryPosition(k),
+                        RingRole::Exterior,
+                        CoordinatePosition(None),
+                    ),
+                };
+                out.push(ProblemAtPosition(
+                    Problem::ElementsOverlaps { other },
+                    ctx.wrap(ProblemPosition::GeometryCollection(
+                        GeometryPosition(i),
+                        Box::new(position),
+                    )),
+                ));
+            }
+        }
+    }
+
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if let Some(max) = config.max_collection_elements {
+            match element_beyond(self, max, &mut 0) {
+                Some(position) => out.push(ProblemAtPosition(
+                    Problem::CollectionTooLarge,
+                    ctx.wrap(position),
+                )),
+                None => self.collect_structural_into(out, &without_element_cap(config), ctx),
             }
+            return;
+        }
+
+        if config.check_empty_geometries && self.0.is_empty() {
+            out.push(ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ctx.wrap(ProblemPosition::Whole),
+            ));
         }
-        // Return the reason(s) of invalidity, or None if valid
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
+        for (i, geometry) in self.0.iter().enumerate() {
+            geometry.collect_structural_into(
+                out,
+                config,
+                PositionContext::GeometryCollection(i, &ctx),
+            );
         }
     }
 }
 
+/// Return the same configuration, without limit on the number of elements of GeometryCollections.
+fn without_element_cap(config: &ValidationConfig) -> ValidationConfig {
+    ValidationConfig {
+        max_collection_elements: None,
+        ..config.clone()
+    }
+}
+
+/// Return the position of the first element (which is not a GeometryCollection) exceeding
+/// `max` elements in the tree of nested GeometryCollections, `seen` being the number of
+/// elements already encountered (or None if there are no more than `max` elements).
+fn element_beyond<T: GeoFloat>(
+    gc: &GeometryCollection<T>,
+    max: usize,
+    seen: &mut usize,
+) -> Option<ProblemPosition> {
+    for (i, geometry) in gc.0.iter().enumerate() {
+        let position = match geometry {
+            Geometry::GeometryCollection(nested) => element_beyond(nested, max, seen),
+            _ => {
+                *seen += 1;
+                (*seen > max).then(|| whole_geometry_position(geometry))
+            }
+        };
+        if let Some(position) = position {
+            return Some(ProblemPosition::GeometryCollection(
+                GeometryPosition(i),
+                Box::new(position),
+            ));
+        }
+    }
+    None
+}
+
+/// Return a position designating a whole geometry (or its first element for multi-geometries).
+fn whole_geometry_position<T: GeoFloat>(geometry: &Geometry<T>) -> ProblemPosition {
+    let any = CoordinatePosition(None);
+    match geometry {
+        Geometry::Point(_) => ProblemPosition::Point,
+        Geometry::Line(_) => ProblemPosition::Line(any),
+        Geometry::Rect(_) => ProblemPosition::Rect(any),
+        Geometry::Triangle(_) => ProblemPosition::Triangle(any),
+        Geometry::LineString(_) => ProblemPosition::LineString(any),
+        Geometry::Polygon(_) => ProblemPosition::Polygon(RingRole::Exterior, any),
+        Geometry::MultiPoint(_) => ProblemPosition::MultiPoint(GeometryPosition(0)),
+        Geometry::MultiLineString(_) => ProblemPosition::MultiLineString(GeometryPosition(0), any),
+        Geometry::MultiPolygon(_) => {
+            ProblemPosition::MultiPolygon(GeometryPosition(0), RingRole::Exterior, any)
+        }
+        Geometry::GeometryCollection(_) => unreachable!(),
+    }
+}
+
+/// Return the index of the members of a GeometryCollection (and the index of the Polygon
+/// for MultiPolygon members) whose area overlaps the area of a different member,
+/// along with the lowest index of the other members it overlaps.
+///
+/// The Polygons with non-finite coordinates (which are reported distinctly) are ignored.
+fn overlapping_members<T: GeoFloat>(
+    gc: &GeometryCollection<T>,
+) -> BTreeMap<(usize, Option<usize>), usize> {
+    let polygons: Vec<(usize, Option<usize>, &Polygon<T>)> =
+        gc.0.iter()
+            .enumerate()
+            .flat_map(|(i, geometry)| match geometry {
+                Geometry::Polygon(polygon) => vec![(i, None, polygon)],
+                Geometry::MultiPolygon(multipolygon) => multipolygon
+                    .0
+                    .iter()
+                    .enumerate()
+                    .map(|(k, polygon)| (i, Some(k), polygon))
+                    .collect(),
+                _ => vec![],
+            })
+            .collect();
+
+    let tree = RTree::bulk_load(
+        polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, polygon))| {
+                polygon
+                    .coords_iter()
+                    .all(|c| c.x.is_finite() && c.y.is_finite())
+            })
+            .filter_map(|(n, (_, _, polygon))| {
+                let rect = polygon.bounding_rect()?;
+                Some(GeomWithData::new(
+                    Rectangle::from_corners(rect.min().x_y(), rect.max().x_y()),
+                    n,
+                ))
+            })
+            .collect(),
+    );
+
+    let mut overlapping = BTreeMap::new();
+    for candidate in tree.iter() {
+        let n = candidate.data;
+        let (i, part, polygon) = polygons[n];
+        let envelope = AABB::from_corners(candidate.geom().lower(), candidate.geom().upper());
+        for other in tree.locate_in_envelope_intersecting(&envelope) {
+            let (i2, part2, polygon2) = polygons[other.data];
+            // Only compare the Polygons of different members, once
+            if i2 <= i {
+                continue;
+            }
+            if profile::timed(Check::Relate, || polygon.relate(polygon2))
+                .get(CoordPos::Inside, CoordPos::Inside)
+                == Dimensions::TwoDimensional
+            {
+                for (key, other) in [((i, part), i2), ((i2, part2), i)] {
+                    let first = overlapping.entry(key).or_insert(other);
+                    *first = other.min(*first);
+                }
+            }
+        }
+    }
+    overlapping
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
-        ProblemReport, Valid,
+        CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemFlags,
+        ProblemKind, ProblemPosition, ProblemReport, RingRole, StrictnessProfile, Valid,
+        ValidationConfig,
+    };
+    use geo_types::{
+        Coord, Geometry, GeometryCollection, LineString, MultiPolygon, Point, Polygon, Triangle,
     };
-    use geo_types::{Coord, Geometry, GeometryCollection, LineString, Point};
     use geos::Geom;
 
+    #[test]
+    fn test_geometrycollection_out_of_geographic_bounds() {
+        let gc = GeometryCollection(vec![
+            Geometry::Point(Point::new(200., 45.)),
+            Geometry::LineString(LineString::from(vec![(0., 0.), (10., 95.)])),
+            Geometry::Triangle(Triangle::from([(-180., -90.), (180., -90.), (0., 90.)])),
+        ]);
+        // The coordinates are finite, so this is valid by default
+        assert!(gc.is_valid());
+
+        let config = ValidationConfig {
+            geographic: true,
+            ..Default::default()
+        };
+        assert!(!gc.is_valid_with(&config));
+        assert_eq!(
+            gc.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(0),
+                        Box::new(ProblemPosition::Point)
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(1),
+                        Box::new(ProblemPosition::LineString(CoordinatePosition(Some(1))))
+                    )
+                ),
+            ]))
+        );
+    }
+
     #[test]
     fn test_geometrycollection_contain_invalid_element() {
         let gc = GeometryCollection(vec![
@@ -68,7 +360,7 @@ mod tests {
                 Problem::TooFewPoints,
                 ProblemPosition::GeometryCollection(
                     GeometryPosition(2),
-                    Box::new(ProblemPosition::LineString(CoordinatePosition(0)))
+                    Box::new(ProblemPosition::LineString(CoordinatePosition(Some(0))))
                 )
             )]))
         );
@@ -91,4 +383,240 @@ mod tests {
             geos::Geometry::create_geometry_collection(geoms).unwrap();
         assert_eq!(gc.is_valid(), geometrycollection_geos.is_valid());
     }
+
+    #[test]
+    fn test_geometrycollection_problem_flags() {
+        // A self-intersecting Polygon and a Triangle with collinear points
+        let gc = GeometryCollection(vec![
+            Geometry::Polygon(Polygon::new(
+                LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+                vec![],
+            )),
+            Geometry::Triangle(Triangle::from([(0., 0.), (1., 1.), (2., 2.)])),
+        ]);
+        let flags = gc.problem_flags();
+        assert_eq!(
+            flags,
+            ProblemFlags::SELF_INTERSECTION | ProblemFlags::COLLINEAR_COORDS
+        );
+        assert_eq!(flags.bits().count_ones(), 2);
+
+        let gc = GeometryCollection(vec![Geometry::Point(Point::new(0., 0.))]);
+        assert!(gc.problem_flags().is_empty());
+    }
+
+    #[test]
+    fn test_geometrycollection_primary_issue() {
+        // A self-intersecting Polygon and a Triangle with collinear points
+        let gc = GeometryCollection(vec![
+            Geometry::Triangle(Triangle::from([(0., 0.), (1., 1.), (2., 2.)])),
+            Geometry::Polygon(Polygon::new(
+                LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+                vec![],
+            )),
+        ]);
+        let problems: Vec<Problem> = gc
+            .explain_invalidity()
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|problem| problem.0)
+            .collect();
+        assert_eq!(
+            problems,
+            vec![Problem::CollinearCoords, Problem::SelfIntersection]
+        );
+        // The self-intersection is the most severe problem
+        assert_eq!(gc.primary_issue(), Some(ProblemKind::SelfIntersection));
+    }
+
+    #[test]
+    fn test_geometrycollection_too_large() {
+        // 10 nested GeometryCollections of 10 Points each
+        let gc = GeometryCollection(
+            (0..10)
+                .map(|i| {
+                    Geometry::GeometryCollection(GeometryCollection(
+                        (0..10)
+                            .map(|j| Geometry::Point(Point::new(i as f64, j as f64)))
+                            .collect(),
+                    ))
+                })
+                .collect(),
+        );
+        assert!(gc.is_valid());
+
+        let config = ValidationConfig {
+            max_collection_elements: Some(64),
+            ..Default::default()
+        };
+        assert!(!gc.is_valid_with(&config));
+        // The 65th Point is the 5th Point of the 7th nested GeometryCollection
+        assert_eq!(
+            gc.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::CollectionTooLarge,
+                ProblemPosition::GeometryCollection(
+                    GeometryPosition(6),
+                    Box::new(ProblemPosition::GeometryCollection(
+                        GeometryPosition(4),
+                        Box::new(ProblemPosition::Point)
+                    ))
+                )
+            )]))
+        );
+
+        // Deep but small collections are accepted
+        let mut deep = GeometryCollection(vec![Geometry::Point(Point::new(0., 0.))]);
+        for _ in 0..100 {
+            deep = GeometryCollection(vec![Geometry::GeometryCollection(deep)]);
+        }
+        assert!(deep.is_valid_with(&config));
+
+        let config = ValidationConfig {
+            max_collection_elements: Some(100),
+            ..Default::default()
+        };
+        assert!(gc.is_valid_with(&config));
+        assert!(gc.explain_invalidity_with(&config).is_none());
+    }
+
+    #[test]
+    fn test_geometrycollection_overlapping_members() {
+        let square = |x0: f64, y0: f64, x1: f64, y1: f64| {
+            Polygon::new(
+                LineString::from(vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1), (x0, y0)]),
+                vec![],
+            )
+        };
+        // The first member overlaps the second Polygon of the third member
+        let gc = GeometryCollection(vec![
+            Geometry::Polygon(square(0., 0., 2., 2.)),
+            Geometry::Point(Point::new(1., 1.)),
+            Geometry::MultiPolygon(MultiPolygon(vec![
+                square(5., 5., 6., 6.),
+                square(1., 1., 3., 3.),
+            ])),
+        ]);
+
+        // This is valid by default
+        assert!(gc.is_valid());
+        assert!(gc.explain_invalidity().is_none());
+
+        let config = ValidationConfig {
+            check_overlapping_collection_members: true,
+            ..Default::default()
+        };
+        assert!(!gc.is_valid_with(&config));
+        assert_eq!(
+            gc.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::ElementsOverlaps { other: 2 },
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(0),
+                        Box::new(ProblemPosition::Polygon(
+                            RingRole::Exterior,
+                            CoordinatePosition(None)
+                        ))
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::ElementsOverlaps { other: 0 },
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(2),
+                        Box::new(ProblemPosition::MultiPolygon(
+                            GeometryPosition(1),
+                            RingRole::Exterior,
+                            CoordinatePosition(None)
+                        ))
+                    )
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_geometrycollection_overlapping_members_with_nan() {
+        // Enough Polygons for the R-tree to be split into several nodes
+        let mut polygons: Vec<Geometry> = (0..20)
+            .map(|i| {
+                let x = i as f64 * 2.;
+                Geometry::Polygon(Polygon::new(
+                    LineString::from(vec![(x, 0.), (x + 1., 0.), (x + 1., 1.), (x, 1.), (x, 0.)]),
+                    vec![],
+                ))
+            })
+            .collect();
+        polygons.push(Geometry::Polygon(Polygon::new(
+            LineString::from(vec![(0., 0.), (1., f64::NAN), (1., 1.), (0., 1.), (0., 0.)]),
+            vec![],
+        )));
+        let gc = GeometryCollection(polygons);
+        let config = StrictnessProfile::Strict.config();
+        assert!(!gc.is_valid_with(&config));
+        assert_eq!(
+            gc.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::GeometryCollection(
+                    GeometryPosition(20),
+                    Box::new(ProblemPosition::Polygon(
+                        RingRole::Exterior,
+                        CoordinatePosition(Some(1))
+                    ))
+                )
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_geometrycollection_empty_elements() {
+        let gc = GeometryCollection(vec![
+            Geometry::Point(Point::new(0., 0.)),
+            Geometry::GeometryCollection(GeometryCollection(vec![])),
+            Geometry::MultiPolygon(MultiPolygon(vec![])),
+        ]);
+        // Empty geometries are only reported when enabled in the ValidationConfig
+        assert!(gc.is_valid());
+        assert!(gc.explain_invalidity().is_none());
+
+        let config = ValidationConfig {
+            check_empty_geometries: true,
+            ..Default::default()
+        };
+        assert!(!gc.is_valid_with(&config));
+        assert_eq!(
+            gc.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::EmptyGeometry,
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(1),
+                        Box::new(ProblemPosition::Whole)
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::EmptyGeometry,
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(2),
+                        Box::new(ProblemPosition::Whole)
+                    )
+                ),
+            ]))
+        );
+
+        let empty = GeometryCollection::<f64>(vec![]);
+        assert_eq!(
+            empty.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ProblemPosition::Whole
+            )]))
+        );
+        assert_eq!(
+            empty.explain_invalidity_with(&config).unwrap().to_string(),
+            "Geometry is empty"
+        );
+    }
 }
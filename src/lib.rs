@@ -7,24 +7,187 @@
 //! - `is_valid()` which returns a boolean,
 //! - `explain_invalidity()` which returns a ProblemReport (a vector of problems, each one with its position in the geometry) that implements the Display trait.
 //!
+//! Both methods have a `_with` variant (`is_valid_with()` and `explain_invalidity_with()`)
+//! taking a ValidationConfig to enable some optional checks
+//! (e.g. `ValidationConfig::ogc()` follows the rules of OGC Simple Features instead of PostGIS).
+//! The `validate` function is a single entry point for geometries with f32 or f64 coordinates,
+//! and the `validate_all` function validates a slice of geometries.
+//! The Valid trait is also implemented for references to geometries.
+//!
+//! The `validate()` method returns a Result whose error (InvalidGeometry) implements
+//! `std::error::Error`, so that the `?` operator can be used.
+//! `Validated::new()` also checks the validity of a geometry, wrapping it in a `Validated`
+//! to let the functions taking it skip checking it again.
+//!
+//! The `ogc_reason()` method returns the first reason of invalidity worded as by
+//! PostGIS `ST_IsValidReason`.
+//!
+//! The `first_problem()` method returns the first problem of `explain_invalidity()`,
+//! stopping the checks as soon as it is found.
+//!
+//! The `quick_check()` method returns a single problem, looking for the structural problems
+//! (such as non-finite coordinates) before running the more expensive relational checks.
+//! Similarly, the `primary_issue()` method returns the category of the most severe problem
+//! (see `ProblemKind`), e.g. to bucket the features of a dataset by their main issue.
+//!
+//! The `problem_flags()` method returns the set of the types of problems of a geometry
+//! as bitflags (see `ProblemFlags`), which can be stored as an integer.
+//!
+//! Valid Polygons and MultiPolygons can also be brought to a canonical form (see the Canonicalize trait),
+//! which is handy to compare or store geometries.
+//!
+//! The messages of a ProblemReport can be translated by implementing the ProblemMessages trait
+//! (see `ProblemReport::render_with`), and `Problem::message()` returns the description
+//! of a problem without its position.
+//!
+//! The `minimal_failing_subgeometry()` method extracts the smallest geometry reproducing
+//! the first problem of a geometry (e.g. the two segments of a self-intersection).
+//!
+//! The problems of a ProblemReport can be sorted by location (see `ProblemReport::sort_spatially`)
+//! and the coordinate designated by the position of a problem can be retrieved with the CoordAt trait
+//! (or the component it designates, such as a ring, with `extract_at`).
+//!
+//! The `assert_valid!`, `assert_invalid!` and `assert_invalid_with!` macros can be used in tests to print the
+//! ProblemReport when the assertion fails.
+//!
+//! Polygons made invalid by imprecise coordinates (near-duplicate or near-collinear vertices,
+//! rings not closed by a tiny gap) can be repaired with `make_valid_snapped`
+//! (see the SnapRepair trait), which changes their coordinates.
+//!
+//! The PolygonDiagnostics trait gives some information (such as the signed area of each ring)
+//! to help understanding why a Polygon is invalid.
+//!
+//! The TriangulationValidity trait checks whether a Polygon can be safely triangulated
+//! (e.g. as a triangle fan, for rendering).
+//!
+//! The Simplicity trait checks whether LineStrings and MultiLineStrings are simple
+//! (i.e. don't cross themselves), which is distinct from their validity.
+//!
+//! The SnapValidity trait checks whether a geometry stays valid once its coordinates
+//! are snapped to a grid (as done by many databases on import). Its `snap_to_grid()` method
+//! returns the snapped geometry, which fixes many problems caused by imprecise coordinates.
+//!
+//! To check that an operation didn't corrupt a geometry, `validate_diff` reports the problems
+//! of the resulting geometry, distinguishing the ones introduced by the operation.
+//!
+//! The structural checks of very large MultiPolygons can be performed without loading them in
+//! memory with `validate_structure`, reading their coordinates from a CoordSource (such as
+//! `FlatCoords`, over a memory-mapped file).
+//!
+//! Sets of Polygons that should form a planar coverage (no gaps nor overlaps between adjacent
+//! Polygons) can be checked with `validate_planar_coverage`.
+//!
+//! Bespoke validity rules can be implemented with the CustomRule trait and checked along with
+//! the built-in checks by `explain_invalidity_with_rules`.
+//!
+//! With the `geojson` feature, the features of a GeoJSON FeatureCollection can be validated
+//! with `validate_feature_collection`.
+//!
+//! With the `geos` feature, `agrees_with_geos` checks that this crate and GEOS agree on
+//! the validity of a geometry.
+//!
+//! With the `parallel` feature, the elements of MultiPolygons and GeometryCollections are
+//! validated concurrently (with rayon), the problems being reported in the same order.
+//!
+//! With the `profiling` feature, `explain_invalidity_profiled` also returns the time spent
+//! in each category of checks (only the time spent in the calling thread being recorded).
+//!
+//! With the `sketch` feature, `ProblemReport::sketch` draws a small ASCII sketch of a geometry
+//! on which the location of each problem is marked.
+//!
+//! With the `tracing` feature, a `tracing` event is emitted for each problem found by
+//! `explain_invalidity`.
+//!
+//! With the `serde` feature, ProblemReports (and the problems and positions they are made of)
+//! can be serialized and deserialized, e.g. to return them as JSON.
+//!
+//! With the `wkt` feature, WKT strings can be validated with `validate_wkt`: each problem is
+//! then reported with the byte offsets of the offending part of the source text.
+//!
+mod canonicalize;
+mod collect;
+mod config;
 mod coord;
+mod coverage;
+mod cow;
+mod diff;
+#[cfg(feature = "geojson")]
+mod geojson;
 mod geometry;
 mod geometrycollection;
 mod line;
 mod linestring;
+mod locate;
+#[macro_use]
+mod macros;
+mod messages;
 mod multilinestring;
 mod multipoint;
 mod multipolygon;
+mod ogc;
+mod parallel;
+#[cfg(feature = "geos")]
+mod parity;
+mod plausible;
 mod point;
 mod polygon;
+mod profile;
 mod rect;
+mod reference;
+mod repair;
+mod reproduce;
+mod rules;
+mod simple;
+#[cfg(feature = "sketch")]
+mod sketch;
+mod snap;
+mod source;
 mod triangle;
+mod triangulation;
 mod utils;
+mod validated;
+#[cfg(feature = "wkt")]
+mod wkt;
 
+use geo_types::Geometry;
 use std::boxed::Box;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq)]
+#[cfg(feature = "geojson")]
+pub use crate::geojson::validate_feature_collection;
+#[cfg(feature = "wkt")]
+pub use crate::wkt::{validate_wkt, validate_wkt_with, ProblemInSource, WktValidationError};
+pub use canonicalize::{normalize_ring_closure, normalize_signed_zeros, Canonicalize};
+pub use config::{RingClosure, RingOrientation, StrictnessProfile, ValidationConfig};
+pub use coverage::validate_planar_coverage;
+pub use diff::validate_diff;
+pub use geometry::{validate, validate_all};
+pub use line::LineDegeneracy;
+pub use locate::{extract_at, CoordAt};
+pub use messages::{EnglishMessages, ProblemMessages};
+pub use multipoint::PointsWithinMask;
+pub use parallel::MaybeSync;
+#[cfg(feature = "geos")]
+pub use parity::agrees_with_geos;
+pub use plausible::StructurallyPlausible;
+pub use polygon::PolygonDiagnostics;
+#[cfg(feature = "profiling")]
+pub use profile::ProfileStats;
+pub use repair::SnapRepair;
+pub use rules::CustomRule;
+pub use simple::Simplicity;
+pub use snap::SnapValidity;
+pub use source::{validate_structure, CoordSource, FlatCoords};
+pub use triangulation::{TriMode, TriangulationValidity};
+pub use validated::Validated;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "index")
+)]
 /// The role of a ring in a polygon.
 pub enum RingRole {
     Exterior,
@@ -41,16 +204,23 @@ impl std::fmt::Display for RingRole {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The position of the problem in a multi-geometry, starting at 0.
-pub struct GeometryPosition(usize);
+pub struct GeometryPosition(pub usize);
 
 #[derive(Debug, PartialEq)]
-/// The coordinate position of the problem in the geometry.
-/// If the value is 0 or more, it is the index of the coordinate.
-/// If the value is -1 it indicates that the coordinate position is not relevant or unknown.
-pub struct CoordinatePosition(isize);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The coordinate position of the problem in the geometry: the index of the coordinate,
+/// or None if the coordinate position is not relevant or unknown (e.g. for a problem
+/// concerning a whole ring).
+pub struct CoordinatePosition(pub Option<usize>);
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "indices")
+)]
 /// The position of the problem in the geometry.
 pub enum ProblemPosition {
     Point,
@@ -63,9 +233,17 @@ pub enum ProblemPosition {
     Polygon(RingRole, CoordinatePosition),
     MultiPolygon(GeometryPosition, RingRole, CoordinatePosition),
     GeometryCollection(GeometryPosition, Box<ProblemPosition>),
+    /// The whole geometry (for the problems of empty multi-geometries and
+    /// GeometryCollections, which have no element to designate)
+    Whole,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type")
+)]
 /// The type of problem encountered.
 pub enum Problem {
     /// A coordinate is not finite (NaN or infinite)
@@ -74,25 +252,355 @@ pub enum Problem {
     TooFewPoints,
     /// Identical coords
     IdenticalCoords,
-    /// Collinear coords
+    /// Collinear coords (reported at the vertex lying between the two others)
     CollinearCoords,
-    /// A ring has a self-intersection
+    /// A ring has a self-intersection (reported at the first coordinate of the first
+    /// segment crossing another segment of the ring)
     SelfIntersection,
+    /// A Polygon ring was not closed (only reported when enabled in the ValidationConfig)
+    RingNotClosed,
     /// Two interior rings of a Polygon share a common line
     IntersectingRingsOnALine,
     /// Two interior rings of a Polygon share a common area
     IntersectingRingsOnAnArea,
     /// The interior ring of a Polygon is not contained in the exterior ring
     InteriorRingNotContainedInExteriorRing,
+    /// The interior ring of a Polygon crosses the exterior ring (it lies partly inside
+    /// and partly outside of it), this is reported in addition to
+    /// `InteriorRingNotContainedInExteriorRing`
+    InteriorRingCrossesExterior,
+    /// All the vertices of an interior ring of a Polygon are on the exterior ring,
+    /// so that it has no area
+    DegenerateHole,
+    /// An interior ring of a Polygon is contained in another interior ring, or contains it
+    /// (this can't be represented by a single Polygon), `other` being the index of the
+    /// other interior ring
+    NestedHoles { other: usize },
     /// Two Polygons of a MultiPolygon overlap partially
-    ElementsOverlaps,
+    /// (`other` being the index of the other Polygon, or of the other member
+    /// of a GeometryCollection)
+    ElementsOverlaps { other: usize },
     /// Two Polygons of a MultiPolygon touch on a line
-    ElementsTouchOnALine,
+    /// (`other` being the index of the other Polygon)
+    ElementsTouchOnALine { other: usize },
     /// Two Polygons of a MultiPolygon are identical
-    ElementsAreIdentical,
+    /// (`other` being the index of the other Polygon)
+    ElementsAreIdentical { other: usize },
+    /// Two Polygons of a coverage are separated by a gap narrower than the tolerance
+    CoverageGap,
+    /// A Polygon of a MultiPolygon lies inside an interior ring of another Polygon
+    /// of the MultiPolygon (only reported when enabled in the ValidationConfig),
+    /// `other` being the index of the other Polygon
+    ExteriorRingInsideHole { other: usize },
+    /// The interior of a Polygon is not connected (e.g. an interior ring touches the exterior
+    /// ring at two points, or, only when enabled in the ValidationConfig, two interior rings
+    /// and the exterior ring meet at a single point)
+    DisconnectedInterior,
+    /// Two Polygons of a MultiPolygon declare the same interior ring
+    /// (`other` being the index of the other Polygon, and `other_ring` the index
+    /// of the interior ring in the other Polygon)
+    SharedHoleBetweenParts { other: usize, other_ring: usize },
+    /// A coordinate of a Polygon is too large for the relations between its rings
+    /// (or with other Polygons) to be computed reliably
+    CoordinateMagnitudeUnsafe,
+    /// A Polygon is not convex (only reported when enabled in the ValidationConfig)
+    NotConvex,
+    /// A coordinate contains a negative zero (only reported when enabled
+    /// in the ValidationConfig)
+    SignedZero,
+    /// A coordinate is not finite while it was finite before an operation
+    /// (see `validate_diff`)
+    IntroducedNotFinite,
+    /// A ring has a self-intersection that it didn't have before an operation
+    /// (see `validate_diff`)
+    IntroducedSelfIntersection,
+    /// A Polygon ring is not wound in the expected direction
+    /// (see `TriangulationValidity`, only reported when enabled in the ValidationConfig)
+    IncorrectRingOrientation,
+    /// A Polygon has an interior ring while it is required not to have any
+    /// (see `TriangulationValidity`)
+    InteriorRingNotAllowed,
+    /// A Polygon ring touches itself at a vertex, forming two loops wound in opposite
+    /// directions (one of them is probably an interior ring encoded in the ring),
+    /// this is reported in addition to `SelfIntersection`
+    SelfTouchingRing,
+    /// A point lies outside of the Polygon it is required to lie within
+    /// (see `PointsWithinMask`)
+    OutsideMask,
+    /// An interior ring of a Polygon is wound in the same direction as the exterior ring,
+    /// so that it may be drawn as a filled island (only reported when enabled
+    /// in the ValidationConfig)
+    HoleRenderedAsIsland,
+    /// A problem found by a user-provided rule (see `CustomRule`),
+    /// identified by its own code
+    Custom { code: String },
+    /// The whole tree of nested GeometryCollections has more elements than allowed
+    /// (only reported when enabled in the ValidationConfig), the position designating
+    /// the first element beyond the limit
+    CollectionTooLarge,
+    /// Two LineStrings of a MultiLineString share a collinear stretch (only reported
+    /// when enabled in the ValidationConfig), the position designating the first segment
+    /// of each LineString lying on the shared stretch (`other` being the index of the
+    /// other LineString)
+    OverlappingEdges { other: usize },
+    /// The interior rings of a Polygon enclose more area, in total, than its exterior ring
+    /// (the area of the Polygon would be negative)
+    HoleAreaExceedsShell,
+    /// A coordinate is identical to the previous one in a LineString or a Polygon ring,
+    /// which is valid but redundant (only reported when enabled in the ValidationConfig)
+    RepeatedPoint,
+    /// A multi-geometry or a GeometryCollection has no element (only reported when enabled
+    /// in the ValidationConfig)
+    EmptyGeometry,
+    /// The minimum corner of a Rect lies beyond its maximum corner (`min().x > max().x`
+    /// or `min().y > max().y`), which the constructors of geo-types prevent but which
+    /// deserialized data may contain
+    InvalidRectOrdering,
+    /// An interior ring of a Polygon covers the same area as the exterior ring
+    /// (e.g. it is a copy of the exterior ring), so that the Polygon has no area
+    DegeneratePolygon,
+    /// A ring of a Polygon goes back on itself, forming a zero-width spike
+    /// (reported at the apex of the spike, instead of a `SelfIntersection`)
+    Spike,
+    /// A ring of a Polygon encloses no area (e.g. all its vertices are collinear),
+    /// see `ValidationConfig::zero_area_tolerance`
+    ZeroArea,
+    /// A coordinate is outside of the range of the geographic coordinates (a longitude
+    /// outside of [-180, 180] or a latitude outside of [-90, 90]), only reported when
+    /// enabled in the ValidationConfig
+    OutOfGeographicBounds,
+    /// A segment of a LineString or of a Polygon ring spans more than 180° of longitude,
+    /// so that it should most probably cross the antimeridian (only reported when enabled
+    /// in the ValidationConfig), the position designating the first coordinate of the segment
+    AntimeridianCrossing,
+}
+
+impl Problem {
+    /// A stable, machine-readable code identifying the type of problem
+    /// (e.g. `"self_intersection"`), suitable for metrics or filtering.
+    ///
+    /// All the problems found by custom rules share the `"custom"` code,
+    /// their own code being stored in `Problem::Custom`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Problem::NotFinite => "not_finite",
+            Problem::TooFewPoints => "too_few_points",
+            Problem::IdenticalCoords => "identical_coords",
+            Problem::CollinearCoords => "collinear_coords",
+            Problem::SelfIntersection => "self_intersection",
+            Problem::RingNotClosed => "ring_not_closed",
+            Problem::IntersectingRingsOnALine => "intersecting_rings_on_a_line",
+            Problem::IntersectingRingsOnAnArea => "intersecting_rings_on_an_area",
+            Problem::InteriorRingNotContainedInExteriorRing => {
+                "interior_ring_not_contained_in_exterior_ring"
+            }
+            Problem::InteriorRingCrossesExterior => "interior_ring_crosses_exterior",
+            Problem::DegenerateHole => "degenerate_hole",
+            Problem::NestedHoles { .. } => "nested_holes",
+            Problem::ElementsOverlaps { .. } => "elements_overlaps",
+            Problem::ElementsTouchOnALine { .. } => "elements_touch_on_a_line",
+            Problem::ElementsAreIdentical { .. } => "elements_are_identical",
+            Problem::CoverageGap => "coverage_gap",
+            Problem::ExteriorRingInsideHole { .. } => "exterior_ring_inside_hole",
+            Problem::DisconnectedInterior => "disconnected_interior",
+            Problem::SharedHoleBetweenParts { .. } => "shared_hole_between_parts",
+            Problem::CoordinateMagnitudeUnsafe => "coordinate_magnitude_unsafe",
+            Problem::NotConvex => "not_convex",
+            Problem::SignedZero => "signed_zero",
+            Problem::IntroducedNotFinite => "introduced_not_finite",
+            Problem::IntroducedSelfIntersection => "introduced_self_intersection",
+            Problem::IncorrectRingOrientation => "incorrect_ring_orientation",
+            Problem::InteriorRingNotAllowed => "interior_ring_not_allowed",
+            Problem::SelfTouchingRing => "self_touching_ring",
+            Problem::OutsideMask => "outside_mask",
+            Problem::HoleRenderedAsIsland => "hole_rendered_as_island",
+            Problem::Custom { .. } => "custom",
+            Problem::CollectionTooLarge => "collection_too_large",
+            Problem::OverlappingEdges { .. } => "overlapping_edges",
+            Problem::HoleAreaExceedsShell => "hole_area_exceeds_shell",
+            Problem::RepeatedPoint => "repeated_point",
+            Problem::EmptyGeometry => "empty_geometry",
+            Problem::InvalidRectOrdering => "invalid_rect_ordering",
+            Problem::DegeneratePolygon => "degenerate_polygon",
+            Problem::Spike => "spike",
+            Problem::ZeroArea => "zero_area",
+            Problem::OutOfGeographicBounds => "out_of_geographic_bounds",
+            Problem::AntimeridianCrossing => "antimeridian_crossing",
+        }
+    }
+
+    /// The bit corresponding to the type of problem (see `ProblemFlags`).
+    pub fn flag(&self) -> ProblemFlags {
+        match self {
+            Problem::NotFinite => ProblemFlags::NOT_FINITE,
+            Problem::TooFewPoints => ProblemFlags::TOO_FEW_POINTS,
+            Problem::IdenticalCoords => ProblemFlags::IDENTICAL_COORDS,
+            Problem::CollinearCoords => ProblemFlags::COLLINEAR_COORDS,
+            Problem::SelfIntersection => ProblemFlags::SELF_INTERSECTION,
+            Problem::RingNotClosed => ProblemFlags::RING_NOT_CLOSED,
+            Problem::IntersectingRingsOnALine => ProblemFlags::INTERSECTING_RINGS_ON_A_LINE,
+            Problem::IntersectingRingsOnAnArea => ProblemFlags::INTERSECTING_RINGS_ON_AN_AREA,
+            Problem::InteriorRingNotContainedInExteriorRing => {
+                ProblemFlags::INTERIOR_RING_NOT_CONTAINED_IN_EXTERIOR_RING
+            }
+            Problem::InteriorRingCrossesExterior => ProblemFlags::INTERIOR_RING_CROSSES_EXTERIOR,
+            Problem::DegenerateHole => ProblemFlags::DEGENERATE_HOLE,
+            Problem::NestedHoles { .. } => ProblemFlags::NESTED_HOLES,
+            Problem::ElementsOverlaps { .. } => ProblemFlags::ELEMENTS_OVERLAPS,
+            Problem::ElementsTouchOnALine { .. } => ProblemFlags::ELEMENTS_TOUCH_ON_A_LINE,
+            Problem::ElementsAreIdentical { .. } => ProblemFlags::ELEMENTS_ARE_IDENTICAL,
+            Problem::CoverageGap => ProblemFlags::COVERAGE_GAP,
+            Problem::ExteriorRingInsideHole { .. } => ProblemFlags::EXTERIOR_RING_INSIDE_HOLE,
+            Problem::DisconnectedInterior => ProblemFlags::DISCONNECTED_INTERIOR,
+            Problem::SharedHoleBetweenParts { .. } => ProblemFlags::SHARED_HOLE_BETWEEN_PARTS,
+            Problem::CoordinateMagnitudeUnsafe => ProblemFlags::COORDINATE_MAGNITUDE_UNSAFE,
+            Problem::NotConvex => ProblemFlags::NOT_CONVEX,
+            Problem::SignedZero => ProblemFlags::SIGNED_ZERO,
+            Problem::IntroducedNotFinite => ProblemFlags::INTRODUCED_NOT_FINITE,
+            Problem::IntroducedSelfIntersection => ProblemFlags::INTRODUCED_SELF_INTERSECTION,
+            Problem::IncorrectRingOrientation => ProblemFlags::INCORRECT_RING_ORIENTATION,
+            Problem::InteriorRingNotAllowed => ProblemFlags::INTERIOR_RING_NOT_ALLOWED,
+            Problem::SelfTouchingRing => ProblemFlags::SELF_TOUCHING_RING,
+            Problem::OutsideMask => ProblemFlags::OUTSIDE_MASK,
+            Problem::HoleRenderedAsIsland => ProblemFlags::HOLE_RENDERED_AS_ISLAND,
+            Problem::Custom { .. } => ProblemFlags::CUSTOM,
+            Problem::CollectionTooLarge => ProblemFlags::COLLECTION_TOO_LARGE,
+            Problem::OverlappingEdges { .. } => ProblemFlags::OVERLAPPING_EDGES,
+            Problem::HoleAreaExceedsShell => ProblemFlags::HOLE_AREA_EXCEEDS_SHELL,
+            Problem::RepeatedPoint => ProblemFlags::REPEATED_POINT,
+            Problem::EmptyGeometry => ProblemFlags::EMPTY_GEOMETRY,
+            Problem::InvalidRectOrdering => ProblemFlags::INVALID_RECT_ORDERING,
+            Problem::DegeneratePolygon => ProblemFlags::DEGENERATE_POLYGON,
+            Problem::Spike => ProblemFlags::SPIKE,
+            Problem::ZeroArea => ProblemFlags::ZERO_AREA,
+            Problem::OutOfGeographicBounds => ProblemFlags::OUT_OF_GEOGRAPHIC_BOUNDS,
+            Problem::AntimeridianCrossing => ProblemFlags::ANTIMERIDIAN_CROSSING,
+        }
+    }
+
+    /// The category of the problem (see `ProblemKind`).
+    pub fn kind(&self) -> ProblemKind {
+        match self {
+            Problem::NotFinite
+            | Problem::IntroducedNotFinite
+            | Problem::CoordinateMagnitudeUnsafe
+            | Problem::OutOfGeographicBounds => ProblemKind::InvalidCoordinate,
+            Problem::TooFewPoints
+            | Problem::IdenticalCoords
+            | Problem::CollinearCoords
+            | Problem::RingNotClosed
+            | Problem::EmptyGeometry
+            | Problem::InvalidRectOrdering
+            | Problem::ZeroArea => ProblemKind::Degenerate,
+            Problem::SelfIntersection
+            | Problem::IntroducedSelfIntersection
+            | Problem::SelfTouchingRing
+            | Problem::Spike => ProblemKind::SelfIntersection,
+            Problem::IntersectingRingsOnALine
+            | Problem::IntersectingRingsOnAnArea
+            | Problem::InteriorRingNotContainedInExteriorRing
+            | Problem::InteriorRingCrossesExterior
+            | Problem::DegenerateHole
+            | Problem::DegeneratePolygon
+            | Problem::NestedHoles { .. }
+            | Problem::DisconnectedInterior
+            | Problem::HoleAreaExceedsShell => ProblemKind::RingRelation,
+            Problem::ElementsOverlaps { .. }
+            | Problem::ElementsTouchOnALine { .. }
+            | Problem::ElementsAreIdentical { .. }
+            | Problem::CoverageGap
+            | Problem::ExteriorRingInsideHole { .. }
+            | Problem::SharedHoleBetweenParts { .. }
+            | Problem::OutsideMask
+            | Problem::OverlappingEdges { .. } => ProblemKind::ElementRelation,
+            Problem::NotConvex
+            | Problem::SignedZero
+            | Problem::IncorrectRingOrientation
+            | Problem::InteriorRingNotAllowed
+            | Problem::HoleRenderedAsIsland
+            | Problem::Custom { .. }
+            | Problem::CollectionTooLarge
+            | Problem::RepeatedPoint
+            | Problem::AntimeridianCrossing => ProblemKind::Convention,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// A set of types of problems, one bit corresponding to each variant of `Problem`
+    /// (see `Valid::problem_flags`), e.g. to store a compact quality flag per feature
+    /// as an integer (with `ProblemFlags::bits`).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ProblemFlags: u64 {
+        const NOT_FINITE = 1 << 0;
+        const TOO_FEW_POINTS = 1 << 1;
+        const IDENTICAL_COORDS = 1 << 2;
+        const COLLINEAR_COORDS = 1 << 3;
+        const SELF_INTERSECTION = 1 << 4;
+        const RING_NOT_CLOSED = 1 << 5;
+        const INTERSECTING_RINGS_ON_A_LINE = 1 << 6;
+        const INTERSECTING_RINGS_ON_AN_AREA = 1 << 7;
+        const INTERIOR_RING_NOT_CONTAINED_IN_EXTERIOR_RING = 1 << 8;
+        const INTERIOR_RING_CROSSES_EXTERIOR = 1 << 9;
+        const DEGENERATE_HOLE = 1 << 10;
+        const NESTED_HOLES = 1 << 11;
+        const ELEMENTS_OVERLAPS = 1 << 12;
+        const ELEMENTS_TOUCH_ON_A_LINE = 1 << 13;
+        const ELEMENTS_ARE_IDENTICAL = 1 << 14;
+        const COVERAGE_GAP = 1 << 15;
+        const EXTERIOR_RING_INSIDE_HOLE = 1 << 16;
+        const DISCONNECTED_INTERIOR = 1 << 17;
+        const SHARED_HOLE_BETWEEN_PARTS = 1 << 18;
+        const COORDINATE_MAGNITUDE_UNSAFE = 1 << 19;
+        const NOT_CONVEX = 1 << 20;
+        const SIGNED_ZERO = 1 << 21;
+        const INTRODUCED_NOT_FINITE = 1 << 22;
+        const INTRODUCED_SELF_INTERSECTION = 1 << 23;
+        const INCORRECT_RING_ORIENTATION = 1 << 24;
+        const INTERIOR_RING_NOT_ALLOWED = 1 << 25;
+        const SELF_TOUCHING_RING = 1 << 26;
+        const OUTSIDE_MASK = 1 << 27;
+        const HOLE_RENDERED_AS_ISLAND = 1 << 28;
+        const CUSTOM = 1 << 29;
+        const COLLECTION_TOO_LARGE = 1 << 30;
+        const OVERLAPPING_EDGES = 1 << 31;
+        const HOLE_AREA_EXCEEDS_SHELL = 1 << 32;
+        const REPEATED_POINT = 1 << 33;
+        const EMPTY_GEOMETRY = 1 << 34;
+        const INVALID_RECT_ORDERING = 1 << 35;
+        const DEGENERATE_POLYGON = 1 << 36;
+        const SPIKE = 1 << 37;
+        const ZERO_AREA = 1 << 38;
+        const OUT_OF_GEOGRAPHIC_BOUNDS = 1 << 39;
+        const ANTIMERIDIAN_CROSSING = 1 << 40;
+    }
+}
+
+/// The categories of problems, from the most severe to the least severe
+/// (see `Problem::kind` and `Valid::primary_issue`).
+///
+/// A self-intersection is deemed more severe than a degenerate component, so that
+/// a self-intersecting geometry is bucketed as such even if it has collinear points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProblemKind {
+    /// A coordinate is not finite or too large
+    InvalidCoordinate,
+    /// A LineString or a ring intersects itself
+    SelfIntersection,
+    /// A component has too few points, identical or collinear coordinates,
+    /// a ring is not closed or a multi-geometry is empty
+    Degenerate,
+    /// The rings of a Polygon are not correctly related to each other
+    RingRelation,
+    /// The elements of a multi-geometry (or of a coverage) are not correctly
+    /// related to each other
+    ElementRelation,
+    /// An optional requirement is not met (convexity, orientation, etc.)
+    Convention,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A problem, at a given position, encountered when checking the validity of a geometry.
 pub struct ProblemAtPosition(pub Problem, pub ProblemPosition);
 
@@ -104,60 +612,141 @@ impl Display for ProblemAtPosition {
 
 /// All the problems encountered when checking the validity of a geometry.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProblemReport(pub Vec<ProblemAtPosition>);
 
+impl ProblemReport {
+    /// The number of problems of the report.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether the report has no problem.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Iterate over the problems of the report (as `&ProblemAtPosition`),
+    /// in the order in which they were reported.
+    pub fn iter(&self) -> std::slice::Iter<'_, ProblemAtPosition> {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for ProblemReport {
+    type Item = ProblemAtPosition;
+    type IntoIter = std::vec::IntoIter<ProblemAtPosition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ProblemReport {
+    type Item = &'a ProblemAtPosition;
+    type IntoIter = std::slice::Iter<'a, ProblemAtPosition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<ProblemAtPosition> for ProblemReport {
+    fn from_iter<I: IntoIterator<Item = ProblemAtPosition>>(iter: I) -> Self {
+        ProblemReport(iter.into_iter().collect())
+    }
+}
+
+impl std::ops::Index<usize> for ProblemReport {
+    type Output = ProblemAtPosition;
+
+    fn index(&self, index: usize) -> &ProblemAtPosition {
+        &self.0[index]
+    }
+}
+
+impl ProblemPosition {
+    /// Return the index of the coordinate designated by the position, or None if the
+    /// position doesn't designate a coordinate (`CoordinatePosition(None)`, Point, MultiPoint).
+    /// For the GeometryCollections, this is the index in the innermost geometry.
+    pub fn coordinate_index(&self) -> Option<usize> {
+        let coord = match self {
+            ProblemPosition::Point | ProblemPosition::MultiPoint(_) | ProblemPosition::Whole => {
+                return None
+            }
+            ProblemPosition::Line(coord)
+            | ProblemPosition::Triangle(coord)
+            | ProblemPosition::Rect(coord)
+            | ProblemPosition::LineString(coord)
+            | ProblemPosition::MultiLineString(_, coord)
+            | ProblemPosition::Polygon(_, coord)
+            | ProblemPosition::MultiPolygon(_, _, coord) => coord,
+            ProblemPosition::GeometryCollection(_, position) => return position.coordinate_index(),
+        };
+        coord.0
+    }
+
+    /// Return the index of the element of the multi-geometry or of the GeometryCollection
+    /// designated by the position, or None for the other geometries.
+    /// For nested GeometryCollections, this is the index in the outermost one.
+    pub fn geometry_index(&self) -> Option<usize> {
+        match self {
+            ProblemPosition::MultiPoint(geom)
+            | ProblemPosition::MultiLineString(geom, _)
+            | ProblemPosition::MultiPolygon(geom, _, _)
+            | ProblemPosition::GeometryCollection(geom, _) => Some(geom.0),
+            _ => None,
+        }
+    }
+
+    /// Return the role of the ring designated by the position, or None if the position
+    /// doesn't designate a ring of a Polygon (or of a Polygon of a MultiPolygon).
+    pub fn ring_role(&self) -> Option<&RingRole> {
+        match self {
+            ProblemPosition::Polygon(role, _) | ProblemPosition::MultiPolygon(_, role, _) => {
+                Some(role)
+            }
+            ProblemPosition::GeometryCollection(_, position) => position.ring_role(),
+            _ => None,
+        }
+    }
+}
+
 impl Display for ProblemPosition {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut str_buffer: Vec<String> = Vec::new();
         match self {
             ProblemPosition::Point => str_buffer.push(String::new()),
-            ProblemPosition::LineString(coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(String::new())
-                } else {
-                    str_buffer.push(format!(" at coordinate {} of the LineString", coord.0))
-                }
-            }
-            ProblemPosition::Triangle(coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(String::new())
-                } else {
-                    str_buffer.push(format!(" at coordinate {} of the Triangle", coord.0))
-                }
-            }
-            ProblemPosition::Polygon(ring_role, coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(format!(" on the {}", ring_role))
-                } else {
-                    str_buffer.push(format!(" at coordinate {} of the {}", coord.0, ring_role))
-                }
-            }
-            ProblemPosition::MultiPolygon(geom_number, ring_role, coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(format!(
-                        " on the {} of the Polygon n°{} of the MultiPolygon",
-                        ring_role, geom_number.0
-                    ))
-                } else {
-                    str_buffer.push(format!(
-                        " at coordinate {} of the {} of the Polygon n°{} of the MultiPolygon",
-                        coord.0, ring_role, geom_number.0
-                    ))
-                }
-            }
-            ProblemPosition::MultiLineString(geom_number, coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(format!(
-                        " on the LineString n°{} of the MultiLineString",
-                        geom_number.0
-                    ))
-                } else {
-                    str_buffer.push(format!(
-                        " at coordinate {} of the LineString n°{} of the MultiLineString",
-                        coord.0, geom_number.0
-                    ))
-                }
-            }
+            ProblemPosition::LineString(coord) => match coord.0 {
+                None => str_buffer.push(String::new()),
+                Some(i) => str_buffer.push(format!(" at coordinate {} of the LineString", i)),
+            },
+            ProblemPosition::Triangle(coord) => match coord.0 {
+                None => str_buffer.push(String::new()),
+                Some(i) => str_buffer.push(format!(" at coordinate {} of the Triangle", i)),
+            },
+            ProblemPosition::Polygon(ring_role, coord) => match coord.0 {
+                None => str_buffer.push(format!(" on the {}", ring_role)),
+                Some(i) => str_buffer.push(format!(" at coordinate {} of the {}", i, ring_role)),
+            },
+            ProblemPosition::MultiPolygon(geom_number, ring_role, coord) => match coord.0 {
+                None => str_buffer.push(format!(
+                    " on the {} of the Polygon n°{} of the MultiPolygon",
+                    ring_role, geom_number.0
+                )),
+                Some(i) => str_buffer.push(format!(
+                    " at coordinate {} of the {} of the Polygon n°{} of the MultiPolygon",
+                    i, ring_role, geom_number.0
+                )),
+            },
+            ProblemPosition::MultiLineString(geom_number, coord) => match coord.0 {
+                None => str_buffer.push(format!(
+                    " on the LineString n°{} of the MultiLineString",
+                    geom_number.0
+                )),
+                Some(i) => str_buffer.push(format!(
+                    " at coordinate {} of the LineString n°{} of the MultiLineString",
+                    i, geom_number.0
+                )),
+            },
             ProblemPosition::MultiPoint(geom_number) => str_buffer.push(format!(
                 " on the Point n°{} of the MultiPoint",
                 geom_number.0
@@ -168,20 +757,15 @@ impl Display for ProblemPosition {
                     *problem_position, geom_number.0
                 ));
             }
-            ProblemPosition::Rect(coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(String::new())
-                } else {
-                    str_buffer.push(format!(" at coordinate {} of the Rect", coord.0))
-                }
-            }
-            ProblemPosition::Line(coord) => {
-                if coord.0 == -1 {
-                    str_buffer.push(String::new())
-                } else {
-                    str_buffer.push(format!(" at coordinate {} of the Line", coord.0))
-                }
-            }
+            ProblemPosition::Rect(coord) => match coord.0 {
+                None => str_buffer.push(String::new()),
+                Some(i) => str_buffer.push(format!(" at coordinate {} of the Rect", i)),
+            },
+            ProblemPosition::Line(coord) => match coord.0 {
+                None => str_buffer.push(String::new()),
+                Some(i) => str_buffer.push(format!(" at coordinate {} of the Line", i)),
+            },
+            ProblemPosition::Whole => str_buffer.push(String::new()),
         }
         write!(f, "{}", str_buffer.join(""))
     }
@@ -189,64 +773,210 @@ impl Display for ProblemPosition {
 
 impl Display for ProblemReport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let buffer =
-            self.0
-                .iter()
-                .map(|p| {
-                    let (problem, position) = (&p.0, &p.1);
-                    let mut str_buffer: Vec<String> = Vec::new();
-                    let is_polygon = matches!(
-                        position,
-                        ProblemPosition::Polygon(_, _) | ProblemPosition::MultiPolygon(_, _, _)
-                    );
-
-                    str_buffer.push(format!("{}", position));
-
-                    match *problem {
-                        Problem::NotFinite => str_buffer
-                            .push("Coordinate is not finite (NaN or infinite)".to_string()),
-                        Problem::TooFewPoints => {
-                            if is_polygon {
-                                str_buffer.push("Polygon ring has too few points".to_string())
-                            } else {
-                                str_buffer.push("LineString has too few points".to_string())
-                            }
-                        }
-                        Problem::IdenticalCoords => str_buffer.push("Identical coords".to_string()),
-                        Problem::CollinearCoords => str_buffer.push("Collinear coords".to_string()),
-                        Problem::SelfIntersection => {
-                            str_buffer.push("Ring has a self-intersection".to_string())
-                        }
-                        Problem::IntersectingRingsOnALine => str_buffer.push(
-                            "Two interior rings of a Polygon share a common line".to_string(),
-                        ),
-                        Problem::IntersectingRingsOnAnArea => str_buffer.push(
-                            "Two interior rings of a Polygon share a common area".to_string(),
-                        ),
-                        Problem::InteriorRingNotContainedInExteriorRing => str_buffer.push(
-                            "The interior ring of a Polygon is not contained in the exterior ring"
-                                .to_string(),
-                        ),
-                        Problem::ElementsOverlaps => str_buffer
-                            .push("Two Polygons of MultiPolygons overlap partially".to_string()),
-                        Problem::ElementsTouchOnALine => str_buffer
-                            .push("Two Polygons of MultiPolygons touch on a line".to_string()),
-                        Problem::ElementsAreIdentical => str_buffer
-                            .push("Two Polygons of MultiPolygons are identical".to_string()),
-                    };
-                    str_buffer.into_iter().rev().collect::<Vec<_>>().join("")
-                })
-                .collect::<Vec<String>>()
-                .join("\n");
-
-        write!(f, "{}", buffer)
+        write!(f, "{}", self.render_with(&EnglishMessages))
+    }
+}
+
+/// The error returned by `Valid::validate` for an invalid geometry,
+/// holding the problems encountered.
+#[derive(Debug, PartialEq)]
+pub struct InvalidGeometry(pub ProblemReport);
+
+impl Display for InvalidGeometry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
+impl std::error::Error for InvalidGeometry {}
+
 /// A trait to check if a geometry is valid and report the reason(s) of invalidity.
 pub trait Valid {
     /// Check if the geometry is valid.
-    fn is_valid(&self) -> bool;
+    fn is_valid(&self) -> bool {
+        self.is_valid_with(&ValidationConfig::postgis())
+    }
     /// Return the reason(s) of invalidity of the geometry, or None if valid.
-    fn explain_invalidity(&self) -> Option<ProblemReport>;
+    fn explain_invalidity(&self) -> Option<ProblemReport> {
+        self.explain_invalidity_with(&ValidationConfig::postgis())
+    }
+    /// Check if the geometry is valid, performing the checks enabled in `config`.
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool;
+    /// Return the reason(s) of invalidity of the geometry, or None if valid,
+    /// performing the checks enabled in `config`.
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport>;
+    /// Return the number of problems of each type (keyed by `Problem::code()`),
+    /// an empty map meaning that the geometry is valid.
+    fn problem_counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        if let Some(report) = self.explain_invalidity() {
+            for ProblemAtPosition(problem, _) in report.0 {
+                *counts.entry(problem.code()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+    /// Return `Ok(())` if the geometry is valid, or an error holding the reason(s) of
+    /// invalidity (as `explain_invalidity`), e.g. to use the `?` operator.
+    fn validate(&self) -> Result<(), InvalidGeometry> {
+        match self.explain_invalidity() {
+            None => Ok(()),
+            Some(report) => Err(InvalidGeometry(report)),
+        }
+    }
+    /// Return the set of the types of problems of the geometry (see `ProblemFlags`),
+    /// an empty set meaning that the geometry is valid.
+    fn problem_flags(&self) -> ProblemFlags {
+        self.explain_invalidity()
+            .map(|report| {
+                report
+                    .0
+                    .iter()
+                    .fold(ProblemFlags::empty(), |flags, problem| {
+                        flags | problem.0.flag()
+                    })
+            })
+            .unwrap_or_else(ProblemFlags::empty)
+    }
+    /// Check the validity of the geometry as cheaply as possible, returning a single
+    /// problem if the geometry is invalid.
+    ///
+    /// The structural checks (finiteness of the coordinates, number of points,
+    /// closure of the rings, etc.) are run first, on each component of the geometry
+    /// in order, and the first structural problem found is returned.
+    /// The relational checks (self-intersections, relations between the rings of Polygons
+    /// and between the elements of multi-geometries) are only run if there is no
+    /// structural problem, in which case the first problem they find is returned.
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        match self.explain_invalidity() {
+            None => Ok(()),
+            Some(report) => Err(report.0.into_iter().next().unwrap()),
+        }
+    }
+    /// Return the first problem of the geometry (the first one of `explain_invalidity`),
+    /// or None if valid.
+    ///
+    /// For Polygons and MultiPolygons, the checks stop as soon as a problem is found,
+    /// which is faster than `explain_invalidity` for invalid geometries.
+    fn first_problem(&self) -> Option<ProblemAtPosition> {
+        self.explain_invalidity()
+            .and_then(|report| report.0.into_iter().next())
+    }
+    /// Return the category of the most severe problem of the geometry (the first one
+    /// in the order of `ProblemKind`), or None if valid.
+    ///
+    /// The relational checks are only run if there is no invalid coordinate.
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        self.explain_invalidity()?
+            .0
+            .iter()
+            .map(|problem| problem.0.kind())
+            .min()
+    }
+    /// Return the first reason of invalidity of the geometry, or None if valid, worded
+    /// as by PostGIS `ST_IsValidReason` (e.g. `"Self-intersection[2 1]"`).
+    ///
+    /// The coordinate between brackets is the coordinate designated by the position
+    /// of the problem, or a coordinate of the component it concerns
+    /// (the self-intersection point for self-intersecting rings).
+    fn ogc_reason(&self) -> Option<String>
+    where
+        Self: CoordAt<f64>,
+    {
+        ogc::ogc_reason(self)
+    }
+    /// Return the smallest geometry reproducing the first reason of invalidity of the
+    /// geometry (e.g. to file a bug or write a regression test), or None if valid:
+    /// - for a self-intersecting ring or LineString, the two segments intersecting each
+    ///   other, as a MultiLineString,
+    /// - for an interior ring not contained in the exterior ring, a Polygon made of the
+    ///   exterior ring and of this interior ring only,
+    /// - otherwise, the component concerned by the problem (as a LineString),
+    ///   or the Point it designates.
+    fn minimal_failing_subgeometry(&self) -> Option<Geometry>
+    where
+        Self: CoordAt<f64>,
+    {
+        reproduce::minimal_failing_subgeometry(self)
+    }
+    /// Return the reason(s) of invalidity of the geometry found by the built-in checks
+    /// (with the default ValidationConfig) followed by the ones found by each of the
+    /// user-provided `rules`, or None if there is none.
+    fn explain_invalidity_with_rules(
+        &self,
+        rules: &[Box<dyn CustomRule>],
+    ) -> Option<Vec<ProblemAtPosition>>
+    where
+        Self: Clone + Into<Geometry>,
+    {
+        rules::explain_invalidity_with_rules(self, rules)
+    }
+    /// Return the reason(s) of invalidity of the geometry (or None if valid), along with
+    /// the time spent in each category of checks (self-intersections, relate computations
+    /// and containment tests).
+    #[cfg(feature = "profiling")]
+    fn explain_invalidity_profiled(&self) -> (Option<Vec<ProblemAtPosition>>, ProfileStats) {
+        profile::record(|| self.explain_invalidity().map(|report| report.0))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::{
+        CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
+        ProblemReport, RingRole, Valid,
+    };
+    use geo_types::{LineString, MultiPolygon, Polygon};
+
+    #[test]
+    fn test_problem_report_serde_round_trip() {
+        let report = ProblemReport(vec![
+            ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0))),
+            ),
+            ProblemAtPosition(
+                Problem::ElementsOverlaps { other: 2 },
+                ProblemPosition::GeometryCollection(
+                    GeometryPosition(1),
+                    Box::new(ProblemPosition::MultiPolygon(
+                        GeometryPosition(0),
+                        RingRole::Interior(3),
+                        CoordinatePosition(None),
+                    )),
+                ),
+            ),
+            ProblemAtPosition(
+                Problem::Custom {
+                    code: "too_small".to_string(),
+                },
+                ProblemPosition::Point,
+            ),
+        ]);
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            json,
+            r#"[[{"type":"SelfIntersection"},{"type":"Polygon","indices":[{"type":"Exterior"},0]}],"#
+                .to_owned()
+                + r#"[{"type":"ElementsOverlaps","other":2},{"type":"GeometryCollection","indices":[1,{"type":"MultiPolygon","indices":[0,{"type":"Interior","index":3},null]}]}],"#
+                + r#"[{"type":"Custom","code":"too_small"},{"type":"Point"}]]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ProblemReport>(&json).unwrap(),
+            report
+        );
+
+        // The report of a geometry
+        let square = Polygon::new(
+            LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]),
+            vec![],
+        );
+        let mp = MultiPolygon(vec![square.clone(), square]);
+        let report = mp.explain_invalidity().unwrap();
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ProblemReport>(&json).unwrap(),
+            report
+        );
+    }
 }
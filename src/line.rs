@@ -1,65 +1,116 @@
+use crate::collect::{self, CollectProblems, PositionContext};
 use crate::{
     utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    ValidationConfig,
 };
 use geo::GeoFloat;
-use geo_types::Line;
+use geo_types::{CoordFloat, Line};
 use num_traits::FromPrimitive;
 
+/// The degeneracy rule shared by the checks of the geometries made of segments
+/// (Line, Triangle and Rect): a segment must not have a zero length.
+pub trait LineDegeneracy {
+    /// Check if the Line has a zero length, i.e. if its start and end coordinates are
+    /// identical (or at most at `tolerance` from each other, if `tolerance` is not 0).
+    fn is_degenerate(&self, tolerance: f64) -> bool;
+}
+
+impl<T: CoordFloat> LineDegeneracy for Line<T> {
+    fn is_degenerate(&self, tolerance: f64) -> bool {
+        utils::check_coords_are_identical(&self.start, &self.end, tolerance)
+    }
+}
+
 impl<T> Valid for Line<T>
 where
     T: GeoFloat + FromPrimitive,
 {
-    fn is_valid(&self) -> bool {
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
         if utils::check_coord_is_not_finite(&self.start)
             || utils::check_coord_is_not_finite(&self.end)
         {
             return false;
         }
+        if [self.start, self.end].iter().any(|coord| {
+            utils::check_coord_has_negative_zero(coord, config)
+                || utils::check_coord_out_of_geographic_bounds(coord, config)
+        }) {
+            return false;
+        }
 
-        if self.start == self.end {
+        if self.is_degenerate(config.near_duplicate_tolerance) {
             return false;
         }
 
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
 
+impl<T> CollectProblems for Line<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
         if utils::check_coord_is_not_finite(&self.start) {
-            reason.push(ProblemAtPosition(
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Line(CoordinatePosition(0)),
+                ctx.wrap(ProblemPosition::Line(CoordinatePosition(Some(0)))),
             ));
         }
         if utils::check_coord_is_not_finite(&self.end) {
-            reason.push(ProblemAtPosition(
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Line(CoordinatePosition(1)),
+                ctx.wrap(ProblemPosition::Line(CoordinatePosition(Some(1)))),
             ));
         }
+        for (i, coord) in [self.start, self.end].iter().enumerate() {
+            if utils::check_coord_has_negative_zero(coord, config) {
+                out.push(ProblemAtPosition(
+                    Problem::SignedZero,
+                    ctx.wrap(ProblemPosition::Line(CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_out_of_geographic_bounds(coord, config) {
+                out.push(ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ctx.wrap(ProblemPosition::Line(CoordinatePosition(Some(i)))),
+                ));
+            }
+        }
 
-        if self.start == self.end {
-            reason.push(ProblemAtPosition(
+        if self.is_degenerate(config.near_duplicate_tolerance) {
+            out.push(ProblemAtPosition(
                 Problem::IdenticalCoords,
-                ProblemPosition::Line(CoordinatePosition(0)),
+                ctx.wrap(ProblemPosition::Line(CoordinatePosition(Some(0)))),
             ));
         }
-
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
-        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+        CoordinatePosition, LineDegeneracy, Problem, ProblemAtPosition, ProblemPosition,
+        ProblemReport, Valid,
     };
     use geo_types::Line;
 
+    #[test]
+    fn test_line_is_degenerate() {
+        let l = Line::new((0., 0.), (1e-10, 0.));
+        assert!(!l.is_degenerate(0.));
+        assert!(l.is_degenerate(1e-9));
+        assert!(Line::new((1., 1.), (1., 1.)).is_degenerate(0.));
+    }
+
     #[test]
     fn test_line_valid() {
         let l = Line::new((0., 0.), (1., 1.));
@@ -75,7 +126,7 @@ mod tests {
             l.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Line(CoordinatePosition(1)),
+                ProblemPosition::Line(CoordinatePosition(Some(1))),
             )]))
         );
     }
@@ -88,7 +139,7 @@ mod tests {
             l.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::IdenticalCoords,
-                ProblemPosition::Line(CoordinatePosition(0)),
+                ProblemPosition::Line(CoordinatePosition(Some(0))),
             )]))
         );
     }
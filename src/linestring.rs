@@ -1,5 +1,7 @@
+use crate::collect::{self, CollectProblems, PositionContext};
 use crate::{
-    utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport,
+    StructurallyPlausible, Valid, ValidationConfig,
 };
 use geo::GeoFloat;
 use geo_types::LineString;
@@ -8,48 +10,125 @@ use num_traits::FromPrimitive;
 /// In postGIS, a LineString is valid if it has at least 2 points
 /// and have a non-zero length (i.e. the first and last points are not the same).
 /// Here we also check that all its points are finite numbers.
-
+///
+/// Optionally (see `ValidationConfig::check_repeated_points`), the consecutive
+/// identical coordinates are reported.
+/// Optionally (see `ValidationConfig::check_linestring_simplicity`), the self-intersecting
+/// LineStrings are reported, as required by OGC Simple Features.
+/// Optionally (see `ValidationConfig::check_antimeridian_crossings`), the segments
+/// spanning more than 180° of longitude are reported.
 impl<T> Valid for LineString<T>
 where
     T: GeoFloat + FromPrimitive,
 {
-    fn is_valid(&self) -> bool {
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        // Cheap rejection of (most of) the geometries with non-finite coordinates
+        if !self.is_structurally_plausible() {
+            return false;
+        }
         if utils::check_too_few_points(self, false) {
             return false;
         }
+        if config.check_repeated_points && utils::repeated_points(self).next().is_some() {
+            return false;
+        }
+        if config.check_antimeridian_crossings
+            && utils::antimeridian_crossings(self).next().is_some()
+        {
+            return false;
+        }
         for coord in &self.0 {
-            if !coord.is_valid() {
+            if !coord.is_valid_with(config) {
                 return false;
             }
         }
+        if config.check_linestring_simplicity && utils::linestring_has_self_intersection(self) {
+            return false;
+        }
         true
     }
 
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
+
+impl<T> CollectProblems for LineString<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        self.collect_structural_into(out, config, ctx);
+
+        if config.check_linestring_simplicity {
+            // Report the first segment crossing another segment, as the Simplicity trait
+            if let Some((segment, _)) = utils::self_intersecting_segments(self) {
+                out.push(ProblemAtPosition(
+                    Problem::SelfIntersection,
+                    ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(
+                        segment.index,
+                    )))),
+                ));
+            }
+        }
+    }
 
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
         // Perform the various checks
         if utils::check_too_few_points(self, false) {
-            reason.push(ProblemAtPosition(
+            out.push(ProblemAtPosition(
                 Problem::TooFewPoints,
-                ProblemPosition::LineString(CoordinatePosition(0)),
+                ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(0)))),
             ));
         }
 
+        if config.check_repeated_points {
+            for i in utils::repeated_points(self) {
+                out.push(ProblemAtPosition(
+                    Problem::RepeatedPoint,
+                    ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(i)))),
+                ));
+            }
+        }
+
+        if config.check_antimeridian_crossings {
+            for i in utils::antimeridian_crossings(self) {
+                out.push(ProblemAtPosition(
+                    Problem::AntimeridianCrossing,
+                    ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(i)))),
+                ));
+            }
+        }
+
         for (i, point) in self.0.iter().enumerate() {
             if utils::check_coord_is_not_finite(point) {
-                reason.push(ProblemAtPosition(
+                out.push(ProblemAtPosition(
                     Problem::NotFinite,
-                    ProblemPosition::LineString(CoordinatePosition(i as isize)),
+                    ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_has_negative_zero(point, config) {
+                out.push(ProblemAtPosition(
+                    Problem::SignedZero,
+                    ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_out_of_geographic_bounds(point, config) {
+                out.push(ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ctx.wrap(ProblemPosition::LineString(CoordinatePosition(Some(i)))),
                 ));
             }
-        }
-
-        // Return the reason(s) of invalidity, or None if valid
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
         }
     }
 }
@@ -58,6 +137,7 @@ where
 mod tests {
     use crate::{
         CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+        ValidationConfig,
     };
     use geo_types::{Coord, LineString};
     use geos::Geom;
@@ -73,6 +153,97 @@ mod tests {
         assert_eq!(ls.is_valid(), linestring_geos.is_valid());
     }
 
+    #[test]
+    fn test_linestring_self_intersection_ogc() {
+        let ls = LineString::from(vec![(0., 0.), (2., 2.), (2., 0.), (0., 2.)]);
+        // PostGIS accepts self-intersecting LineStrings, OGC doesn't
+        assert!(ls.is_valid_with(&ValidationConfig::postgis()));
+        assert!(!ls.is_valid_with(&ValidationConfig::ogc()));
+        assert_eq!(
+            ls.explain_invalidity_with(&ValidationConfig::ogc()),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::LineString(CoordinatePosition(Some(0)))
+            )]))
+        );
+
+        // A closed LineString is simple
+        let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]);
+        assert!(ls.is_valid_with(&ValidationConfig::ogc()));
+    }
+
+    #[test]
+    fn test_linestring_antimeridian_crossing() {
+        let ls = LineString::from(vec![(170., 0.), (179., 0.), (-179., 0.), (-170., 0.)]);
+        // This is valid by default
+        assert!(ls.is_valid());
+
+        let config = ValidationConfig {
+            check_antimeridian_crossings: true,
+            ..Default::default()
+        };
+        assert!(!ls.is_valid_with(&config));
+        assert_eq!(
+            ls.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::AntimeridianCrossing,
+                ProblemPosition::LineString(CoordinatePosition(Some(1)))
+            )]))
+        );
+
+        // The segments with an infinite coordinate are only reported as not finite
+        let ls = LineString::from(vec![(170., 0.), (f64::INFINITY, 0.), (-170., 0.)]);
+        assert_eq!(
+            ls.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::LineString(CoordinatePosition(Some(1)))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_linestring_repeated_point() {
+        let ls = LineString::from(vec![(0., 0.), (0., 0.), (1., 1.), (2., 0.), (2., 0.)]);
+        // This is valid by default
+        assert!(ls.is_valid());
+
+        let config = ValidationConfig {
+            check_repeated_points: true,
+            ..Default::default()
+        };
+        assert!(!ls.is_valid_with(&config));
+        assert_eq!(
+            ls.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::RepeatedPoint,
+                    ProblemPosition::LineString(CoordinatePosition(Some(1)))
+                ),
+                ProblemAtPosition(
+                    Problem::RepeatedPoint,
+                    ProblemPosition::LineString(CoordinatePosition(Some(4)))
+                ),
+            ]))
+        );
+
+        // Repeated points coexist with the other problems
+        let ls = LineString::from(vec![(0., 0.), (0., 0.), (f64::NAN, 1.)]);
+        assert_eq!(
+            ls.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::RepeatedPoint,
+                    ProblemPosition::LineString(CoordinatePosition(Some(1)))
+                ),
+                ProblemAtPosition(
+                    Problem::NotFinite,
+                    ProblemPosition::LineString(CoordinatePosition(Some(2)))
+                ),
+            ]))
+        );
+    }
+
     #[test]
     fn test_linestring_invalid_empty() {
         let ls = LineString(vec![]);
@@ -81,7 +252,7 @@ mod tests {
             ls.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::TooFewPoints,
-                ProblemPosition::LineString(CoordinatePosition(0))
+                ProblemPosition::LineString(CoordinatePosition(Some(0)))
             )]))
         );
 
@@ -98,7 +269,7 @@ mod tests {
             ls.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::TooFewPoints,
-                ProblemPosition::LineString(CoordinatePosition(0))
+                ProblemPosition::LineString(CoordinatePosition(Some(0)))
             )]))
         );
 
@@ -115,7 +286,7 @@ mod tests {
             ls.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::TooFewPoints,
-                ProblemPosition::LineString(CoordinatePosition(0))
+                ProblemPosition::LineString(CoordinatePosition(Some(0)))
             )]))
         );
 
@@ -0,0 +1,368 @@
+use crate::{CoordinatePosition, ProblemPosition, ProblemReport, RingRole};
+use geo_types::{
+    Coord, CoordFloat, CoordNum, Geometry, GeometryCollection, Line, LineString, MultiLineString,
+    MultiPoint, MultiPolygon, Point, Polygon, Rect, Triangle,
+};
+use std::cmp::Ordering;
+
+/// A trait to retrieve the coordinate of a geometry designated by the position of a problem.
+pub trait CoordAt<T: CoordNum> {
+    /// Return the coordinate at the given position, or None if the position doesn't designate
+    /// a single coordinate of the geometry (e.g. when the problem concerns a whole ring).
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>>;
+}
+
+fn ring_coord_at<T: CoordNum>(
+    polygon: &Polygon<T>,
+    ring_role: &RingRole,
+    position: &CoordinatePosition,
+) -> Option<Coord<T>> {
+    let ring = match ring_role {
+        RingRole::Exterior => polygon.exterior(),
+        RingRole::Interior(i) => polygon.interiors().get(*i)?,
+    };
+    ring.0.get(position.0?).copied()
+}
+
+impl<T: CoordNum> CoordAt<T> for Point<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::Point => Some(self.0),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for Line<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::Line(coord) => match coord.0? {
+                0 => Some(self.start),
+                1 => Some(self.end),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for Triangle<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::Triangle(coord) => self.to_array().get(coord.0?).copied(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for Rect<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::Rect(coord) => match coord.0? {
+                0 => Some(self.min()),
+                1 => Some(self.max()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for LineString<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::LineString(coord) => self.0.get(coord.0?).copied(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for Polygon<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::Polygon(ring_role, coord) => ring_coord_at(self, ring_role, coord),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for MultiPoint<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::MultiPoint(geom) => self.0.get(geom.0).map(|p| p.0),
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for MultiLineString<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::MultiLineString(geom, coord) => {
+                self.0.get(geom.0)?.0.get(coord.0?).copied()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for MultiPolygon<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::MultiPolygon(geom, ring_role, coord) => {
+                ring_coord_at(self.0.get(geom.0)?, ring_role, coord)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for GeometryCollection<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match position {
+            ProblemPosition::GeometryCollection(geom, position) => {
+                self.0.get(geom.0)?.coord_at(position)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: CoordNum> CoordAt<T> for Geometry<T> {
+    fn coord_at(&self, position: &ProblemPosition) -> Option<Coord<T>> {
+        match self {
+            Geometry::Point(e) => e.coord_at(position),
+            Geometry::Line(e) => e.coord_at(position),
+            Geometry::Rect(e) => e.coord_at(position),
+            Geometry::Triangle(e) => e.coord_at(position),
+            Geometry::LineString(e) => e.coord_at(position),
+            Geometry::Polygon(e) => e.coord_at(position),
+            Geometry::MultiPoint(e) => e.coord_at(position),
+            Geometry::MultiLineString(e) => e.coord_at(position),
+            Geometry::MultiPolygon(e) => e.coord_at(position),
+            Geometry::GeometryCollection(e) => e.coord_at(position),
+        }
+    }
+}
+
+/// Return the component of `geom` designated by a position (e.g. to draw it):
+/// - the ring of a Polygon (or of a Polygon of a MultiPolygon), as a LineString,
+/// - the LineString of a MultiLineString or the Point of a MultiPoint,
+/// - `geom` itself for the other geometries (and for `ProblemPosition::Whole`),
+///
+/// the positions in GeometryCollections being followed recursively.
+/// The coordinate designated by the position, if any, is ignored (see CoordAt).
+///
+/// Return None if the position doesn't match the shape of `geom` (e.g. a Polygon position
+/// for a LineString, or an index beyond the number of rings or elements).
+pub fn extract_at<T: CoordNum>(
+    geom: &Geometry<T>,
+    position: &ProblemPosition,
+) -> Option<Geometry<T>> {
+    let ring = |polygon: &Polygon<T>, ring_role: &RingRole| {
+        let ring = match ring_role {
+            RingRole::Exterior => polygon.exterior(),
+            RingRole::Interior(i) => polygon.interiors().get(*i)?,
+        };
+        Some(Geometry::LineString(ring.clone()))
+    };
+    match (geom, position) {
+        (_, ProblemPosition::Whole)
+        | (Geometry::Point(_), ProblemPosition::Point)
+        | (Geometry::Line(_), ProblemPosition::Line(_))
+        | (Geometry::Triangle(_), ProblemPosition::Triangle(_))
+        | (Geometry::Rect(_), ProblemPosition::Rect(_))
+        | (Geometry::LineString(_), ProblemPosition::LineString(_)) => Some(geom.clone()),
+        (Geometry::Polygon(p), ProblemPosition::Polygon(ring_role, _)) => ring(p, ring_role),
+        (Geometry::MultiPoint(mp), ProblemPosition::MultiPoint(j)) => {
+            Some(Geometry::Point(*mp.0.get(j.0)?))
+        }
+        (Geometry::MultiLineString(mls), ProblemPosition::MultiLineString(j, _)) => {
+            Some(Geometry::LineString(mls.0.get(j.0)?.clone()))
+        }
+        (Geometry::MultiPolygon(mp), ProblemPosition::MultiPolygon(j, ring_role, _)) => {
+            ring(mp.0.get(j.0)?, ring_role)
+        }
+        (Geometry::GeometryCollection(gc), ProblemPosition::GeometryCollection(j, position)) => {
+            extract_at(gc.0.get(j.0)?, position)
+        }
+        _ => None,
+    }
+}
+
+/// Compare two numbers, NaN being greater than any other number.
+fn cmp_nan_last<T: CoordFloat>(a: T, b: T) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+impl ProblemReport {
+    /// Sort the problems by the location of their coordinate in `geom` (the geometry they
+    /// were reported for), from left to right then from bottom to top.
+    /// The problems that don't concern a single coordinate (such as a whole ring) are
+    /// left at the end, in their original order.
+    pub fn sort_spatially<T, G>(&mut self, geom: &G)
+    where
+        T: CoordFloat,
+        G: CoordAt<T>,
+    {
+        let mut located: Vec<_> = self
+            .0
+            .drain(..)
+            .map(|problem| (geom.coord_at(&problem.1), problem))
+            .collect();
+        located.sort_by(|(a, _), (b, _)| match (a, b) {
+            (Some(a), Some(b)) => cmp_nan_last(a.x, b.x).then(cmp_nan_last(a.y, b.y)),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+        self.0 = located.into_iter().map(|(_, problem)| problem).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        extract_at, CoordAt, CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition,
+        ProblemPosition, ProblemReport, RingRole, Valid,
+    };
+    use geo_types::{coord, Geometry, GeometryCollection, LineString, Point, Polygon};
+
+    #[test]
+    fn test_extract_at() {
+        let exterior = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]);
+        let hole = LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.), (1., 1.)]);
+        let gc = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Point(Point::new(0., 0.)),
+            Geometry::Polygon(Polygon::new(exterior.clone(), vec![hole.clone()])),
+        ]));
+        let in_polygon =
+            |position| ProblemPosition::GeometryCollection(GeometryPosition(1), Box::new(position));
+
+        assert_eq!(
+            extract_at(
+                &gc,
+                &in_polygon(ProblemPosition::Polygon(
+                    RingRole::Interior(0),
+                    CoordinatePosition(Some(2))
+                ))
+            ),
+            Some(Geometry::LineString(hole))
+        );
+        assert_eq!(
+            extract_at(
+                &gc,
+                &in_polygon(ProblemPosition::Polygon(
+                    RingRole::Exterior,
+                    CoordinatePosition(None)
+                ))
+            ),
+            Some(Geometry::LineString(exterior))
+        );
+        assert_eq!(extract_at(&gc, &ProblemPosition::Whole), Some(gc.clone()));
+
+        // The positions not matching the geometry
+        assert_eq!(
+            extract_at(
+                &gc,
+                &in_polygon(ProblemPosition::Polygon(
+                    RingRole::Interior(1),
+                    CoordinatePosition(None)
+                ))
+            ),
+            None
+        );
+        assert_eq!(
+            extract_at(
+                &gc,
+                &in_polygon(ProblemPosition::LineString(CoordinatePosition(None)))
+            ),
+            None
+        );
+        assert_eq!(extract_at(&gc, &ProblemPosition::Point), None);
+    }
+
+    #[test]
+    fn test_problem_position_accessors() {
+        let position = ProblemPosition::GeometryCollection(
+            GeometryPosition(2),
+            Box::new(ProblemPosition::MultiPolygon(
+                GeometryPosition(1),
+                RingRole::Interior(0),
+                CoordinatePosition(Some(3)),
+            )),
+        );
+        assert_eq!(position.coordinate_index(), Some(3));
+        assert_eq!(position.geometry_index(), Some(2));
+        assert_eq!(position.ring_role(), Some(&RingRole::Interior(0)));
+
+        let position = ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None));
+        assert_eq!(position.coordinate_index(), None);
+        assert_eq!(position.geometry_index(), None);
+        assert_eq!(position.ring_role(), Some(&RingRole::Exterior));
+
+        let position = ProblemPosition::MultiPoint(GeometryPosition(4));
+        assert_eq!(position.coordinate_index(), None);
+        assert_eq!(position.geometry_index(), Some(4));
+        assert_eq!(position.ring_role(), None);
+    }
+
+    #[test]
+    fn test_coord_at_geometrycollection() {
+        let gc = GeometryCollection(vec![
+            Geometry::Point(Point::new(0., 0.)),
+            Geometry::LineString(LineString::from(vec![(0., 0.), (1., 2.)])),
+        ]);
+        assert_eq!(
+            gc.coord_at(&ProblemPosition::GeometryCollection(
+                GeometryPosition(1),
+                Box::new(ProblemPosition::LineString(CoordinatePosition(Some(1))))
+            )),
+            Some(coord! { x: 1., y: 2. })
+        );
+        assert_eq!(
+            gc.coord_at(&ProblemPosition::GeometryCollection(
+                GeometryPosition(1),
+                Box::new(ProblemPosition::LineString(CoordinatePosition(None)))
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sort_spatially() {
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (4., f64::NAN),
+                (4., 4.),
+                (f64::NAN, 2.),
+                (1., f64::NAN),
+                (2., 1.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        let mut report = p.explain_invalidity().unwrap();
+        // Add a problem which doesn't concern a single coordinate
+        report.0.insert(
+            0,
+            ProblemAtPosition(Problem::SelfIntersection, at_exterior(None)),
+        );
+        report.sort_spatially(&p);
+        assert_eq!(
+            report,
+            ProblemReport(vec![
+                ProblemAtPosition(Problem::NotFinite, at_exterior(Some(4))),
+                ProblemAtPosition(Problem::NotFinite, at_exterior(Some(1))),
+                ProblemAtPosition(Problem::NotFinite, at_exterior(Some(3))),
+                ProblemAtPosition(Problem::SelfIntersection, at_exterior(None)),
+            ])
+        );
+    }
+
+    fn at_exterior(i: Option<usize>) -> ProblemPosition {
+        ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(i))
+    }
+}
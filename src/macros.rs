@@ -0,0 +1,133 @@
+/// Assert that a geometry is valid, printing the ProblemReport on failure.
+///
+/// ```rust
+/// use geo_validity_check::assert_valid;
+/// use geo_types::Point;
+///
+/// assert_valid!(Point::new(1., 2.));
+/// ```
+#[macro_export]
+macro_rules! assert_valid {
+    ($geom:expr $(,)?) => {
+        if let Some(report) = $crate::Valid::explain_invalidity(&$geom) {
+            panic!(
+                "assertion failed: `{}` is not valid:\n{}",
+                stringify!($geom),
+                report
+            );
+        }
+    };
+}
+
+/// Assert that a geometry is invalid because of (at least) the given Problem,
+/// printing the ProblemReport on failure.
+///
+/// ```rust
+/// use geo_validity_check::{assert_invalid_with, Problem};
+/// use geo_types::Point;
+///
+/// assert_invalid_with!(Point::new(f64::NAN, 2.), Problem::NotFinite);
+/// ```
+#[macro_export]
+macro_rules! assert_invalid_with {
+    ($geom:expr, $problem:expr $(,)?) => {
+        match $crate::Valid::explain_invalidity(&$geom) {
+            None => panic!(
+                "assertion failed: `{}` is valid (expected {:?})",
+                stringify!($geom),
+                $problem
+            ),
+            Some(report) => {
+                if !report.0.iter().any(|p| p.0 == $problem) {
+                    panic!(
+                        "assertion failed: `{}` is invalid but not because of {:?}:\n{}",
+                        stringify!($geom),
+                        $problem,
+                        report
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Assert that a geometry is invalid, printing the geometry on failure.
+///
+/// A Problem can also be given, in which case this is the same as `assert_invalid_with!`.
+///
+/// ```rust
+/// use geo_validity_check::{assert_invalid, Problem};
+/// use geo_types::Point;
+///
+/// assert_invalid!(Point::new(f64::NAN, 2.));
+/// assert_invalid!(Point::new(f64::NAN, 2.), Problem::NotFinite);
+/// ```
+#[macro_export]
+macro_rules! assert_invalid {
+    ($geom:expr $(,)?) => {
+        if $crate::Valid::explain_invalidity(&$geom).is_none() {
+            panic!("assertion failed: `{}` is valid", stringify!($geom));
+        }
+    };
+    ($geom:expr, $problem:expr $(,)?) => {
+        $crate::assert_invalid_with!($geom, $problem)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Problem;
+    use geo_types::{LineString, Polygon};
+
+    fn bowtie() -> Polygon {
+        Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_assert_valid() {
+        assert_valid!(LineString::from(vec![(0., 0.), (1., 1.)]));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "is not valid:\nRing has a self-intersection at coordinate 1 of the exterior ring"
+    )]
+    fn test_assert_valid_panics_with_report() {
+        assert_valid!(bowtie());
+    }
+
+    #[test]
+    fn test_assert_invalid() {
+        assert_invalid!(bowtie());
+        assert_invalid!(bowtie(), Problem::SelfIntersection);
+    }
+
+    #[test]
+    #[should_panic(expected = "is valid")]
+    fn test_assert_invalid_valid_geometry() {
+        assert_invalid!(LineString::from(vec![(0., 0.), (1., 1.)]));
+    }
+
+    #[test]
+    fn test_assert_invalid_with() {
+        assert_invalid_with!(bowtie(), Problem::SelfIntersection);
+    }
+
+    #[test]
+    #[should_panic(expected = "is invalid but not because of TooFewPoints")]
+    fn test_assert_invalid_with_other_problem() {
+        assert_invalid_with!(bowtie(), Problem::TooFewPoints);
+    }
+
+    #[test]
+    #[should_panic(expected = "is valid (expected NotFinite)")]
+    fn test_assert_invalid_with_valid_geometry() {
+        assert_invalid_with!(
+            LineString::from(vec![(0., 0.), (1., 1.)]),
+            Problem::NotFinite
+        );
+    }
+}
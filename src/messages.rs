@@ -0,0 +1,469 @@
+use crate::{Problem, ProblemAtPosition, ProblemPosition, ProblemReport};
+use std::collections::BTreeMap;
+
+/// The wording of the problems of a ProblemReport (see `ProblemReport::render_with`),
+/// which can be implemented to translate them.
+///
+/// `EnglishMessages` is the implementation used to display a ProblemReport.
+pub trait ProblemMessages {
+    /// Return the message describing a problem found at a given position.
+    fn message(&self, problem: &Problem, position: &ProblemPosition) -> String;
+}
+
+/// The English wording of the problems, used to display a ProblemReport.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnglishMessages;
+
+impl Problem {
+    /// Return the English description of the problem, without its position
+    /// (e.g. "Ring has a self-intersection").
+    ///
+    /// The index of the other element concerned by a problem (such as `ElementsOverlaps`)
+    /// and the code of `Custom` problems are not part of this description.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Problem::NotFinite => "Coordinate is not finite (NaN or infinite)",
+            Problem::TooFewPoints => "Geometry component has too few points",
+            Problem::IdenticalCoords => "Identical coords",
+            Problem::CollinearCoords => "Collinear coords",
+            Problem::SelfIntersection => "Ring has a self-intersection",
+            Problem::RingNotClosed => "Polygon ring is not closed",
+            Problem::IntersectingRingsOnALine => {
+                "Two interior rings of a Polygon share a common line"
+            }
+            Problem::IntersectingRingsOnAnArea => {
+                "Two interior rings of a Polygon share a common area"
+            }
+            Problem::InteriorRingNotContainedInExteriorRing => {
+                "The interior ring of a Polygon is not contained in the exterior ring"
+            }
+            Problem::InteriorRingCrossesExterior => {
+                "The interior ring of a Polygon crosses the exterior ring"
+            }
+            Problem::DegenerateHole => {
+                "The interior ring of a Polygon lies entirely on the exterior ring \
+                (it has no area)"
+            }
+            Problem::NestedHoles { .. } => {
+                "An interior ring of a Polygon is nested inside another interior ring \
+                (it should probably be a MultiPolygon)"
+            }
+            Problem::ElementsOverlaps { .. } => "Two Polygons of MultiPolygons overlap partially",
+            Problem::ElementsTouchOnALine { .. } => "Two Polygons of MultiPolygons touch on a line",
+            Problem::OverlappingEdges { .. } => {
+                "LineString shares a collinear stretch with another LineString"
+            }
+            Problem::ElementsAreIdentical { .. } => "Two Polygons of MultiPolygons are identical",
+            Problem::ExteriorRingInsideHole { .. } => {
+                "A Polygon of a MultiPolygon lies inside an interior ring of another Polygon"
+            }
+            Problem::CoverageGap => "Two Polygons of a coverage are separated by a gap",
+            Problem::DisconnectedInterior => "The interior of a Polygon is not connected",
+            Problem::SharedHoleBetweenParts { .. } => {
+                "Two Polygons of a MultiPolygon have the same interior ring"
+            }
+            Problem::CoordinateMagnitudeUnsafe => {
+                "Coordinate is too large to be validated reliably"
+            }
+            Problem::NotConvex => "Polygon is not convex",
+            Problem::SignedZero => "Coordinate contains a negative zero",
+            Problem::IntroducedNotFinite => {
+                "Coordinate is not finite (NaN or infinite) since the operation"
+            }
+            Problem::IntroducedSelfIntersection => {
+                "Ring has a self-intersection since the operation"
+            }
+            Problem::IncorrectRingOrientation => {
+                "Polygon ring is not wound in the expected direction"
+            }
+            Problem::InteriorRingNotAllowed => "Polygon has an interior ring, which is not allowed",
+            Problem::SelfTouchingRing => {
+                "Polygon ring touches itself, forming loops wound in opposite directions \
+                (one of them may be a misencoded interior ring)"
+            }
+            Problem::OutsideMask => "Point lies outside of the mask",
+            Problem::HoleRenderedAsIsland => {
+                "The interior ring of a Polygon is wound in the same direction as the \
+                exterior ring (it may be drawn as an island)"
+            }
+            Problem::Custom { .. } => "Custom rule not followed",
+            Problem::CollectionTooLarge => {
+                "The GeometryCollection has too many elements (they were not validated)"
+            }
+            Problem::RepeatedPoint => "Coordinate is identical to the previous one",
+            Problem::EmptyGeometry => "Geometry is empty",
+            Problem::InvalidRectOrdering => "Rect has its minimum corner beyond its maximum corner",
+            Problem::DegeneratePolygon => {
+                "The interior ring of a Polygon covers the same area as the exterior ring \
+                (the Polygon has no area)"
+            }
+            Problem::Spike => "Ring has a spike (it goes back on itself)",
+            Problem::ZeroArea => "Ring encloses no area",
+            Problem::OutOfGeographicBounds => {
+                "Coordinate is out of the geographic bounds \
+                (longitude in [-180, 180], latitude in [-90, 90])"
+            }
+            Problem::AntimeridianCrossing => {
+                "Segment spans more than 180° of longitude (it should cross the antimeridian)"
+            }
+            Problem::HoleAreaExceedsShell => {
+                "The interior rings of a Polygon enclose more area than its exterior ring"
+            }
+        }
+    }
+}
+
+impl ProblemMessages for EnglishMessages {
+    fn message(&self, problem: &Problem, position: &ProblemPosition) -> String {
+        let is_polygon = matches!(
+            position,
+            ProblemPosition::Polygon(_, _) | ProblemPosition::MultiPolygon(_, _, _)
+        );
+        match problem {
+            Problem::TooFewPoints if is_polygon => {
+                format!("Polygon ring has too few points{}", position)
+            }
+            Problem::TooFewPoints => format!("LineString has too few points{}", position),
+            Problem::NestedHoles { other }
+            | Problem::ElementsOverlaps { other }
+            | Problem::ElementsTouchOnALine { other }
+            | Problem::ElementsAreIdentical { other }
+            | Problem::ExteriorRingInsideHole { other }
+            | Problem::OverlappingEdges { other } => format!(
+                "{} (the other one being n°{}){}",
+                problem.message(),
+                other,
+                position
+            ),
+            Problem::SharedHoleBetweenParts { other, other_ring } => format!(
+                "{} (the other one being the interior ring n°{} of the Polygon n°{}){}",
+                problem.message(),
+                other_ring,
+                other,
+                position
+            ),
+            Problem::Custom { code } => format!("{} ({}){}", problem.message(), code, position),
+            _ => format!("{}{}", problem.message(), position),
+        }
+    }
+}
+
+impl ProblemReport {
+    /// Return the messages describing each problem of the report (one per line),
+    /// worded by `messages` (e.g. a translation of `EnglishMessages`).
+    pub fn render_with(&self, messages: &dyn ProblemMessages) -> String {
+        self.0
+            .iter()
+            .map(|problem| messages.message(&problem.0, &problem.1))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Return the problems of the report grouped by type (keyed by `Problem::code()`),
+    /// in the order in which they were reported.
+    pub fn grouped(&self) -> BTreeMap<&'static str, Vec<&ProblemAtPosition>> {
+        let mut groups: BTreeMap<&'static str, Vec<&ProblemAtPosition>> = BTreeMap::new();
+        for problem in self.0.iter() {
+            groups.entry(problem.0.code()).or_default().push(problem);
+        }
+        groups
+    }
+
+    /// Return a one-line summary of the report, giving the number of problems
+    /// of each type (e.g. `"8 problems of 2 types: 5 not_finite, 3 self_intersection"`).
+    pub fn summary(&self) -> String {
+        let plural = |n: usize, word: &str| match n {
+            1 => format!("{} {}", n, word),
+            _ => format!("{} {}s", n, word),
+        };
+        let groups = self.grouped();
+        let counts = groups
+            .iter()
+            .map(|(code, problems)| format!("{} {}", problems.len(), code))
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!(
+            "{} of {}: {}",
+            plural(self.0.len(), "problem"),
+            plural(groups.len(), "type"),
+            counts
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemMessages,
+        ProblemPosition, ProblemReport, RingRole,
+    };
+
+    /// A partial French translation
+    struct FrenchMessages;
+
+    impl ProblemMessages for FrenchMessages {
+        fn message(&self, problem: &Problem, position: &ProblemPosition) -> String {
+            let text = match problem {
+                Problem::SelfIntersection => "Le contour s'auto-intersecte",
+                Problem::NotFinite => "La coordonnée n'est pas finie",
+                _ => "Problème",
+            };
+            match position {
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None)) => {
+                    format!("{} (contour extérieur)", text)
+                }
+                ProblemPosition::Polygon(_, CoordinatePosition(Some(i))) => {
+                    format!("{} (coordonnée {})", text, i)
+                }
+                _ => text.to_string(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_with_translated_messages() {
+        let report = ProblemReport(vec![
+            ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None)),
+            ),
+            ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(Some(2))),
+            ),
+        ]);
+        assert_eq!(
+            report.render_with(&FrenchMessages),
+            "Le contour s'auto-intersecte (contour extérieur)\n\
+            La coordonnée n'est pas finie (coordonnée 2)"
+        );
+        // The report is displayed with the English messages
+        assert_eq!(
+            report.to_string(),
+            "Ring has a self-intersection on the exterior ring\n\
+            Coordinate is not finite (NaN or infinite) at coordinate 2 of the interior ring n°0"
+        );
+    }
+
+    #[test]
+    fn test_display_line_rect_and_geometrycollection_positions() {
+        let report = ProblemReport(vec![
+            ProblemAtPosition(
+                Problem::IdenticalCoords,
+                ProblemPosition::Line(CoordinatePosition(Some(1))),
+            ),
+            ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::Rect(CoordinatePosition(Some(0))),
+            ),
+            ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::GeometryCollection(
+                    GeometryPosition(2),
+                    Box::new(ProblemPosition::GeometryCollection(
+                        GeometryPosition(0),
+                        Box::new(ProblemPosition::Line(CoordinatePosition(Some(0)))),
+                    )),
+                ),
+            ),
+        ]);
+        assert_eq!(
+            report.to_string(),
+            "Identical coords at coordinate 1 of the Line\n\
+            Coordinate is not finite (NaN or infinite) at coordinate 0 of the Rect\n\
+            Coordinate is not finite (NaN or infinite) at coordinate 0 of the Line \
+            of the geometry n°0 of the GeometryCollection \
+            of the geometry n°2 of the GeometryCollection"
+        );
+    }
+
+    #[test]
+    fn test_grouped_and_summary() {
+        let at = |i| ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(i)));
+        let report = ProblemReport(vec![
+            ProblemAtPosition(Problem::NotFinite, at(1)),
+            ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None)),
+            ),
+            ProblemAtPosition(Problem::NotFinite, at(3)),
+        ]);
+        let groups = report.grouped();
+        assert_eq!(
+            groups.keys().copied().collect::<Vec<_>>(),
+            vec!["not_finite", "self_intersection"]
+        );
+        assert_eq!(groups["not_finite"], vec![&report.0[0], &report.0[2]]);
+        assert_eq!(groups["self_intersection"], vec![&report.0[1]]);
+        assert_eq!(
+            report.summary(),
+            "3 problems of 2 types: 2 not_finite, 1 self_intersection"
+        );
+
+        let report = ProblemReport(vec![ProblemAtPosition(Problem::NotFinite, at(0))]);
+        assert_eq!(report.summary(), "1 problem of 1 type: 1 not_finite");
+    }
+
+    #[test]
+    fn test_problem_report_accessors() {
+        let at = |i| ProblemPosition::LineString(CoordinatePosition(Some(i)));
+        let report: ProblemReport = (0..3)
+            .map(|i| ProblemAtPosition(Problem::NotFinite, at(i)))
+            .collect();
+        assert_eq!(report.len(), 3);
+        assert!(!report.is_empty());
+        assert_eq!(report[1], ProblemAtPosition(Problem::NotFinite, at(1)));
+        assert_eq!(report.iter().count(), 3);
+        for (i, problem) in (&report).into_iter().enumerate() {
+            assert_eq!(problem.1, at(i));
+        }
+        let problems: Vec<ProblemAtPosition> = report.into_iter().collect();
+        assert_eq!(problems.len(), 3);
+        assert!(ProblemReport(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_problem_message() {
+        let messages = [
+            (Problem::NotFinite, "Coordinate is not finite (NaN or infinite)"),
+            (Problem::TooFewPoints, "Geometry component has too few points"),
+            (Problem::IdenticalCoords, "Identical coords"),
+            (Problem::CollinearCoords, "Collinear coords"),
+            (Problem::SelfIntersection, "Ring has a self-intersection"),
+            (Problem::RingNotClosed, "Polygon ring is not closed"),
+            (
+                Problem::IntersectingRingsOnALine,
+                "Two interior rings of a Polygon share a common line",
+            ),
+            (
+                Problem::IntersectingRingsOnAnArea,
+                "Two interior rings of a Polygon share a common area",
+            ),
+            (
+                Problem::InteriorRingNotContainedInExteriorRing,
+                "The interior ring of a Polygon is not contained in the exterior ring",
+            ),
+            (
+                Problem::InteriorRingCrossesExterior,
+                "The interior ring of a Polygon crosses the exterior ring",
+            ),
+            (
+                Problem::DegenerateHole,
+                "The interior ring of a Polygon lies entirely on the exterior ring (it has no \
+                area)",
+            ),
+            (
+                Problem::NestedHoles { other: 1 },
+                "An interior ring of a Polygon is nested inside another interior ring (it should \
+                probably be a MultiPolygon)",
+            ),
+            (
+                Problem::ElementsOverlaps { other: 1 },
+                "Two Polygons of MultiPolygons overlap partially",
+            ),
+            (
+                Problem::ElementsTouchOnALine { other: 1 },
+                "Two Polygons of MultiPolygons touch on a line",
+            ),
+            (
+                Problem::OverlappingEdges { other: 1 },
+                "LineString shares a collinear stretch with another LineString",
+            ),
+            (
+                Problem::ElementsAreIdentical { other: 1 },
+                "Two Polygons of MultiPolygons are identical",
+            ),
+            (
+                Problem::ExteriorRingInsideHole { other: 1 },
+                "A Polygon of a MultiPolygon lies inside an interior ring of another Polygon",
+            ),
+            (Problem::CoverageGap, "Two Polygons of a coverage are separated by a gap"),
+            (Problem::DisconnectedInterior, "The interior of a Polygon is not connected"),
+            (
+                Problem::SharedHoleBetweenParts {
+                    other: 1,
+                    other_ring: 0,
+                },
+                "Two Polygons of a MultiPolygon have the same interior ring",
+            ),
+            (
+                Problem::CoordinateMagnitudeUnsafe,
+                "Coordinate is too large to be validated reliably",
+            ),
+            (Problem::NotConvex, "Polygon is not convex"),
+            (Problem::SignedZero, "Coordinate contains a negative zero"),
+            (
+                Problem::IntroducedNotFinite,
+                "Coordinate is not finite (NaN or infinite) since the operation",
+            ),
+            (
+                Problem::IntroducedSelfIntersection,
+                "Ring has a self-intersection since the operation",
+            ),
+            (
+                Problem::IncorrectRingOrientation,
+                "Polygon ring is not wound in the expected direction",
+            ),
+            (Problem::InteriorRingNotAllowed, "Polygon has an interior ring, which is not allowed"),
+            (
+                Problem::SelfTouchingRing,
+                "Polygon ring touches itself, forming loops wound in opposite directions (one of \
+                them may be a misencoded interior ring)",
+            ),
+            (Problem::OutsideMask, "Point lies outside of the mask"),
+            (
+                Problem::HoleRenderedAsIsland,
+                "The interior ring of a Polygon is wound in the same direction as the exterior \
+                ring (it may be drawn as an island)",
+            ),
+            (
+                Problem::Custom {
+                    code: "rule".to_string(),
+                },
+                "Custom rule not followed",
+            ),
+            (
+                Problem::CollectionTooLarge,
+                "The GeometryCollection has too many elements (they were not validated)",
+            ),
+            (Problem::RepeatedPoint, "Coordinate is identical to the previous one"),
+            (Problem::EmptyGeometry, "Geometry is empty"),
+            (Problem::InvalidRectOrdering, "Rect has its minimum corner beyond its maximum corner"),
+            (
+                Problem::DegeneratePolygon,
+                "The interior ring of a Polygon covers the same area as the exterior ring (the \
+                Polygon has no area)",
+            ),
+            (Problem::Spike, "Ring has a spike (it goes back on itself)"),
+            (Problem::ZeroArea, "Ring encloses no area"),
+            (
+                Problem::OutOfGeographicBounds,
+                "Coordinate is out of the geographic bounds (longitude in [-180, 180], latitude in \
+                [-90, 90])",
+            ),
+            (
+                Problem::AntimeridianCrossing,
+                "Segment spans more than 180° of longitude (it should cross the antimeridian)",
+            ),
+            (
+                Problem::HoleAreaExceedsShell,
+                "The interior rings of a Polygon enclose more area than its exterior ring",
+            ),
+        ];
+        for (problem, message) in messages.iter() {
+            assert_eq!(problem.message(), *message);
+        }
+        // The message is displayed before the position of the problem
+        let report = ProblemReport(vec![ProblemAtPosition(
+            Problem::ElementsOverlaps { other: 1 },
+            ProblemPosition::MultiPolygon(
+                GeometryPosition(0),
+                RingRole::Exterior,
+                CoordinatePosition(None),
+            ),
+        )]);
+        assert!(report.to_string().starts_with(
+            "Two Polygons of MultiPolygons overlap partially (the other one being n°1)"
+        ));
+    }
+}
@@ -1,54 +1,140 @@
-use crate::{GeometryPosition, ProblemAtPosition, ProblemPosition, ProblemReport, Valid};
-use geo::GeoFloat;
-use geo_types::MultiLineString;
+use crate::collect::{self, CollectProblems, PositionContext};
+use crate::profile::{self, Check};
+use crate::{
+    utils, CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
+    ProblemReport, Valid, ValidationConfig,
+};
+use geo::coordinate_position::CoordPos;
+use geo::dimensions::Dimensions;
+use geo::line_intersection::{line_intersection, LineIntersection};
+use geo::{GeoFloat, Relate};
+use geo_types::{LineString, MultiLineString};
 use num_traits::FromPrimitive;
 
 /// MultiLineString is valid if all its LineStrings are valid.
+///
+/// Optionally (see `ValidationConfig::check_overlapping_edges`), the LineStrings sharing
+/// a collinear stretch with another LineString are reported (`OverlappingEdges`, along with
+/// the index of the other LineString), for the consumers requiring the LineStrings
+/// to only meet at points.
 impl<T> Valid for MultiLineString<T>
 where
     T: GeoFloat + FromPrimitive,
 {
-    fn is_valid(&self) -> bool {
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        if config.check_empty_geometries && self.0.is_empty() {
+            return false;
+        }
         for line in &self.0 {
-            if !line.is_valid() {
+            if !line.is_valid_with(config) {
                 return false;
             }
         }
+        if config.check_overlapping_edges && !overlapping_edges(self).is_empty() {
+            return false;
+        }
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
 
+impl<T> CollectProblems for MultiLineString<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if config.check_empty_geometries && self.0.is_empty() {
+            out.push(ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ctx.wrap(ProblemPosition::Whole),
+            ));
+        }
         for (j, line) in self.0.iter().enumerate() {
-            let temp_reason = line.explain_invalidity();
-            if let Some(temp_reason) = temp_reason {
-                for ProblemAtPosition(problem, position) in temp_reason.0 {
-                    match position {
-                        ProblemPosition::LineString(coord_pos) => {
-                            reason.push(ProblemAtPosition(
-                                problem,
-                                ProblemPosition::MultiLineString(GeometryPosition(j), coord_pos),
-                            ));
-                        }
-                        _ => unreachable!(),
-                    }
-                }
+            line.collect_into(out, config, PositionContext::MultiLineString(j, &ctx));
+        }
+
+        if config.check_overlapping_edges {
+            for (j, i, other) in overlapping_edges(self) {
+                out.push(ProblemAtPosition(
+                    Problem::OverlappingEdges { other },
+                    ctx.wrap(ProblemPosition::MultiLineString(
+                        GeometryPosition(j),
+                        CoordinatePosition(i),
+                    )),
+                ));
             }
         }
-        // Return the reason(s) of invalidity, or None if valid
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
+    }
+
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if config.check_empty_geometries && self.0.is_empty() {
+            out.push(ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ctx.wrap(ProblemPosition::Whole),
+            ));
+        }
+        for (j, line) in self.0.iter().enumerate() {
+            line.collect_structural_into(out, config, PositionContext::MultiLineString(j, &ctx));
         }
     }
 }
 
+/// Return, for each pair of LineStrings of a MultiLineString sharing a collinear stretch,
+/// the index of each of both LineStrings along with the index of its first segment lying
+/// on the shared stretch and the index of the other LineString (the LineStrings with
+/// non-finite coordinates being skipped).
+fn overlapping_edges<T: GeoFloat>(mls: &MultiLineString<T>) -> Vec<(usize, Option<usize>, usize)> {
+    let is_finite = |line: &LineString<T>| !line.0.iter().any(utils::check_coord_is_not_finite);
+    let mut overlapping = Vec::new();
+    for (j, line) in mls.0.iter().enumerate() {
+        if !is_finite(line) {
+            continue;
+        }
+        for (k, other) in mls.0.iter().enumerate().skip(j + 1) {
+            if !is_finite(other)
+                || profile::timed(Check::Relate, || line.relate(other))
+                    .get(CoordPos::Inside, CoordPos::Inside)
+                    != Dimensions::OneDimensional
+            {
+                continue;
+            }
+            overlapping.push((j, first_shared_segment(line, other), k));
+            overlapping.push((k, first_shared_segment(other, line), j));
+        }
+    }
+    overlapping
+}
+
+/// Return the index of the first segment of `line` sharing a collinear stretch
+/// with a segment of `other` (or None if there is none).
+fn first_shared_segment<T: GeoFloat>(line: &LineString<T>, other: &LineString<T>) -> Option<usize> {
+    line.lines().position(|segment| {
+        other.lines().any(|other_segment| {
+            matches!(
+                line_intersection(segment, other_segment),
+                Some(LineIntersection::Collinear { .. })
+            )
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
-        ProblemReport, Valid,
+        ProblemReport, Valid, ValidationConfig,
     };
     use geo_types::{Coord, LineString, MultiLineString};
     use geos::Geom;
@@ -80,7 +166,7 @@ mod tests {
             mls.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::TooFewPoints,
-                ProblemPosition::MultiLineString(GeometryPosition(1), CoordinatePosition(0))
+                ProblemPosition::MultiLineString(GeometryPosition(1), CoordinatePosition(Some(0)))
             )]))
         );
 
@@ -88,4 +174,63 @@ mod tests {
         let multilinestring_geos: geos::Geometry = (&mls).try_into().unwrap();
         assert_eq!(mls.is_valid(), multilinestring_geos.is_valid());
     }
+
+    #[test]
+    fn test_multilinestring_f32_too_few_points() {
+        // The second LineString collapses to a single point
+        let mls: MultiLineString<f32> = MultiLineString(vec![
+            LineString::from(vec![(0., 0.), (1., 1.)]),
+            LineString::from(vec![(2., 2.), (2., 2.)]),
+        ]);
+        assert!(!mls.is_valid());
+        assert_eq!(
+            mls.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::TooFewPoints,
+                ProblemPosition::MultiLineString(GeometryPosition(1), CoordinatePosition(Some(0)))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_multilinestring_overlapping_edges() {
+        // Both LineStrings follow the segment from (2, 0) to (3, 0)
+        let mls = MultiLineString(vec![
+            LineString::from(vec![(0., 0.), (1., 0.), (3., 0.)]),
+            LineString::from(vec![(2., 2.), (2., 0.), (4., 0.)]),
+            // Crossing the first LineString at a single point is accepted
+            LineString::from(vec![(0.5, -1.), (0.5, 1.)]),
+        ]);
+        // This is valid by default
+        assert!(mls.is_valid());
+
+        // Test that the multilinestring has the same validity status than its GEOS equivalent
+        let multilinestring_geos: geos::Geometry = (&mls).try_into().unwrap();
+        assert_eq!(mls.is_valid(), multilinestring_geos.is_valid());
+
+        let config = ValidationConfig {
+            check_overlapping_edges: true,
+            ..Default::default()
+        };
+        assert!(!mls.is_valid_with(&config));
+        assert_eq!(
+            mls.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::OverlappingEdges { other: 1 },
+                    ProblemPosition::MultiLineString(
+                        GeometryPosition(0),
+                        CoordinatePosition(Some(1))
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::OverlappingEdges { other: 0 },
+                    ProblemPosition::MultiLineString(
+                        GeometryPosition(1),
+                        CoordinatePosition(Some(1))
+                    )
+                ),
+            ]))
+        );
+    }
 }
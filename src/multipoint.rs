@@ -1,8 +1,11 @@
+use crate::collect::{self, CollectProblems, PositionContext};
 use crate::{
     utils, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    ValidationConfig,
 };
+use geo::coordinate_position::{CoordPos, CoordinatePosition};
 use geo::GeoFloat;
-use geo_types::MultiPoint;
+use geo_types::{MultiPoint, Polygon};
 
 /// In PostGIS, MultiPoint don't have any validity constraint.
 /// Here we choose to check that points are finite numbers (i.e. not NaN or infinite)
@@ -10,31 +13,89 @@ impl<T> Valid for MultiPoint<T>
 where
     T: GeoFloat,
 {
-    fn is_valid(&self) -> bool {
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        if config.check_empty_geometries && self.0.is_empty() {
+            return false;
+        }
         for point in &self.0 {
-            if !point.is_valid() {
+            if !point.is_valid_with(config) {
                 return false;
             }
         }
         true
     }
 
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
 
+impl<T> CollectProblems for MultiPoint<T>
+where
+    T: GeoFloat,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if config.check_empty_geometries && self.0.is_empty() {
+            out.push(ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ctx.wrap(ProblemPosition::Whole),
+            ));
+        }
         for (i, point) in self.0.iter().enumerate() {
             if utils::check_coord_is_not_finite(&point.0) {
-                reason.push(ProblemAtPosition(
+                out.push(ProblemAtPosition(
                     Problem::NotFinite,
-                    ProblemPosition::MultiPoint(GeometryPosition(i)),
+                    ctx.wrap(ProblemPosition::MultiPoint(GeometryPosition(i))),
+                ));
+            }
+            if utils::check_coord_has_negative_zero(&point.0, config) {
+                out.push(ProblemAtPosition(
+                    Problem::SignedZero,
+                    ctx.wrap(ProblemPosition::MultiPoint(GeometryPosition(i))),
+                ));
+            }
+            if utils::check_coord_out_of_geographic_bounds(&point.0, config) {
+                out.push(ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ctx.wrap(ProblemPosition::MultiPoint(GeometryPosition(i))),
                 ));
             }
         }
+    }
+}
 
-        if reason.is_empty() {
+/// Check that the points of a MultiPoint lie within a boundary (such as survey points
+/// that must be inside the surveyed area).
+pub trait PointsWithinMask<T: GeoFloat> {
+    /// Return an `OutsideMask` problem for each point lying outside of `mask`,
+    /// or None if all the points are within `mask` (the points on its boundary
+    /// being considered within it).
+    fn validate_points_within(&self, mask: &Polygon<T>) -> Option<Vec<ProblemAtPosition>>;
+}
+
+impl<T: GeoFloat> PointsWithinMask<T> for MultiPoint<T> {
+    fn validate_points_within(&self, mask: &Polygon<T>) -> Option<Vec<ProblemAtPosition>> {
+        let problems: Vec<ProblemAtPosition> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| mask.coordinate_position(&point.0) == CoordPos::Outside)
+            .map(|(i, _)| {
+                ProblemAtPosition(
+                    Problem::OutsideMask,
+                    ProblemPosition::MultiPoint(GeometryPosition(i)),
+                )
+            })
+            .collect();
+        if problems.is_empty() {
             None
         } else {
-            Some(ProblemReport(reason))
+            Some(problems)
         }
     }
 }
@@ -42,9 +103,10 @@ where
 #[cfg(test)]
 mod tests {
     use crate::{
-        GeometryPosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+        GeometryPosition, PointsWithinMask, Problem, ProblemAtPosition, ProblemPosition,
+        ProblemReport, Valid,
     };
-    use geo_types::{MultiPoint, Point};
+    use geo_types::{LineString, MultiPoint, Point, Polygon};
     use geos::Geom;
 
     #[test]
@@ -83,4 +145,45 @@ mod tests {
         let multipoint_geos: geos::Geometry = (&mp).try_into().unwrap();
         assert_eq!(mp.is_valid(), multipoint_geos.is_valid());
     }
+
+    #[test]
+    fn test_multipoint_within_mask() {
+        let mask = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (2., 1.),
+                (2., 2.),
+                (1., 2.),
+                (1., 1.),
+            ])],
+        );
+        // Inside, and on the boundary of the exterior and interior rings
+        let mp = MultiPoint(vec![
+            Point::new(3., 3.),
+            Point::new(4., 2.),
+            Point::new(1.5, 1.),
+        ]);
+        assert!(mp.validate_points_within(&mask).is_none());
+
+        // Outside of the exterior ring, and inside the interior ring
+        let mp = MultiPoint(vec![
+            Point::new(3., 3.),
+            Point::new(5., 2.),
+            Point::new(1.5, 1.5),
+        ]);
+        assert_eq!(
+            mp.validate_points_within(&mask),
+            Some(vec![
+                ProblemAtPosition(
+                    Problem::OutsideMask,
+                    ProblemPosition::MultiPoint(GeometryPosition(1))
+                ),
+                ProblemAtPosition(
+                    Problem::OutsideMask,
+                    ProblemPosition::MultiPoint(GeometryPosition(2))
+                )
+            ])
+        );
+    }
 }
@@ -1,128 +1,394 @@
+use crate::collect::{self, CollectProblems, PositionContext};
+use crate::polygon::has_unsafe_coordinates;
+use crate::profile::{self, Check};
 use crate::{
-    CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
-    ProblemReport, RingRole, Valid,
+    CoordinatePosition, GeometryPosition, MaybeSync, Problem, ProblemAtPosition, ProblemKind,
+    ProblemPosition, ProblemReport, RingRole, Valid, ValidationConfig,
 };
 use geo::coordinate_position::CoordPos;
 use geo::dimensions::Dimensions;
-use geo::{GeoFloat, Relate};
-use geo_types::MultiPolygon;
+use geo::{BoundingRect, Contains, GeoFloat, Relate};
+use geo_types::{MultiPolygon, Polygon, Rect};
 use num_traits::FromPrimitive;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
 
 /// MultiPolygon is valid if:
 /// - [x] all its polygons are valid,
 /// - [x] elements do not overlaps (i.e. their interiors must not intersect)
 /// - [x] elements touch only at points
+///
+/// Each pair of elements yields at most one problem, the most specific one:
+/// `ElementsAreIdentical`, then `ElementsOverlaps`, then `ElementsTouchOnALine`
+/// (touching at points being valid), reported on the first element of the pair
+/// (the index of the second one being given by the problem).
+/// Interior rings declared by two different elements are also reported
+/// (`SharedHoleBetweenParts`), unless the elements are identical.
+///
+/// Optionally (see `ValidationConfig::check_islands_in_holes`), elements lying
+/// inside an interior ring of another element are reported.
 impl<T> Valid for MultiPolygon<T>
 where
-    T: GeoFloat + FromPrimitive,
+    T: GeoFloat + FromPrimitive + MaybeSync,
 {
-    fn is_valid(&self) -> bool {
-        for (j, pol) in self.0.iter().enumerate() {
-            if !pol.is_valid() {
-                return false;
-            }
-            for (i, pol2) in self.0.iter().enumerate() {
-                if j != i {
-                    if pol == pol2 {
-                        return false;
-                    }
-                    let im = pol.relate(pol2);
-                    if im.get(CoordPos::Inside, CoordPos::Inside) == Dimensions::TwoDimensional {
-                        return false;
-                    }
-                    if im.get(CoordPos::OnBoundary, CoordPos::OnBoundary)
-                        == Dimensions::OneDimensional
-                    {
-                        return false;
-                    }
-                }
-            }
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        if config.check_empty_geometries && self.0.is_empty() {
+            return false;
         }
-        true
+        let pairs_are_valid = |(j, candidates): (usize, Vec<usize>)| {
+            candidates
+                .into_iter()
+                .all(|i| pair_is_valid(self, j, i, config))
+        };
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            self.0.par_iter().all(|pol| pol.is_valid_with(config))
+                && candidate_pairs(self)
+                    .into_par_iter()
+                    .enumerate()
+                    .all(pairs_are_valid)
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.0.iter().all(|pol| pol.is_valid_with(config))
+                && candidate_pairs(self)
+                    .into_iter()
+                    .enumerate()
+                    .all(pairs_are_valid)
+        }
+    }
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        collect::quick_check(self)
+    }
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        collect::primary_issue(self)
+    }
+    fn first_problem(&self) -> Option<ProblemAtPosition> {
+        collect::first_problem(self)
+    }
+}
+
+impl<T> CollectProblems for MultiPolygon<T>
+where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        collect_multipolygon_problems(self, out, config, ctx, false);
+    }
+
+    fn collect_first_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        collect_multipolygon_problems(self, out, config, ctx, true);
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
 
-        // Loop over all the polygons, collect the reasons of invalidity
-        // and change the ProblemPosition to reflect the MultiPolygon
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if config.check_empty_geometries && self.0.is_empty() {
+            out.push(ProblemAtPosition(
+                Problem::EmptyGeometry,
+                ctx.wrap(ProblemPosition::Whole),
+            ));
+        }
         for (j, polygon) in self.0.iter().enumerate() {
-            let temp_reason = polygon.explain_invalidity();
-            if let Some(temp_reason) = temp_reason {
-                for ProblemAtPosition(problem, position) in temp_reason.0 {
-                    match position {
-                        ProblemPosition::Polygon(ring_role, coord_pos) => {
-                            reason.push(ProblemAtPosition(
-                                problem,
-                                ProblemPosition::MultiPolygon(
-                                    GeometryPosition(j),
-                                    ring_role,
-                                    coord_pos,
-                                ),
-                            ));
-                        }
-                        _ => unreachable!(),
-                    }
-                }
-            }
+            polygon.collect_structural_into(out, config, PositionContext::MultiPolygon(j, &ctx));
+        }
+    }
+}
 
-            // Special case for MultiPolygon: elements must not overlap and must touch only at points
-            for (i, pol2) in self.0.iter().enumerate() {
-                if j != i {
-                    if polygon == pol2 {
-                        reason.push(ProblemAtPosition(
-                            Problem::ElementsAreIdentical,
-                            ProblemPosition::MultiPolygon(
-                                GeometryPosition(j),
-                                RingRole::Exterior,
-                                CoordinatePosition(-1),
-                            ),
-                        ));
-                    } else {
-                        let im = polygon.relate(pol2);
-                        if im.get(CoordPos::Inside, CoordPos::Inside) == Dimensions::TwoDimensional
-                        {
-                            reason.push(ProblemAtPosition(
-                                Problem::ElementsOverlaps,
-                                ProblemPosition::MultiPolygon(
-                                    GeometryPosition(j),
-                                    RingRole::Exterior,
-                                    CoordinatePosition(-1),
-                                ),
-                            ));
-                        }
-                        if im.get(CoordPos::OnBoundary, CoordPos::OnBoundary)
-                            == Dimensions::OneDimensional
-                        {
-                            reason.push(ProblemAtPosition(
-                                Problem::ElementsTouchOnALine,
-                                ProblemPosition::MultiPolygon(
-                                    GeometryPosition(j),
-                                    RingRole::Exterior,
-                                    CoordinatePosition(-1),
-                                ),
-                            ));
-                        }
-                    }
-                }
+/// Push the problems of a MultiPolygon into `out`, stopping as soon as a problem is found
+/// if `first_only` is set (see `CollectProblems::collect_first_into`).
+fn collect_multipolygon_problems<T>(
+    mp: &MultiPolygon<T>,
+    out: &mut Vec<ProblemAtPosition>,
+    config: &ValidationConfig,
+    ctx: PositionContext,
+    first_only: bool,
+) where
+    T: GeoFloat + FromPrimitive + MaybeSync,
+{
+    let n_problems = out.len();
+    let found = |out: &Vec<ProblemAtPosition>| first_only && out.len() > n_problems;
+    if config.check_empty_geometries && mp.0.is_empty() {
+        out.push(ProblemAtPosition(
+            Problem::EmptyGeometry,
+            ctx.wrap(ProblemPosition::Whole),
+        ));
+    }
+    // The problems of each element are collected concurrently, then concatenated
+    // in the order of the elements (as when they are collected sequentially)
+    #[cfg(feature = "parallel")]
+    if !first_only {
+        use rayon::prelude::*;
+        let problems: Vec<Vec<ProblemAtPosition>> = candidate_pairs(mp)
+            .into_par_iter()
+            .enumerate()
+            .map(|(j, candidates)| {
+                let mut element_out = Vec::new();
+                collect_element_problems(mp, j, candidates, &mut element_out, config, &ctx, false);
+                element_out
+            })
+            .collect();
+        out.extend(problems.into_iter().flatten());
+        return;
+    }
+    for (j, candidates) in candidate_pairs(mp).into_iter().enumerate() {
+        collect_element_problems(mp, j, candidates, out, config, &ctx, first_only);
+        if found(out) {
+            return;
+        }
+    }
+}
+
+/// Push the problems of the `j`-th element of a MultiPolygon into `out` (its own problems,
+/// then the ones concerning its relation with the `candidates` elements), stopping as soon
+/// as a problem is found if `first_only` is set.
+fn collect_element_problems<T>(
+    mp: &MultiPolygon<T>,
+    j: usize,
+    candidates: Vec<usize>,
+    out: &mut Vec<ProblemAtPosition>,
+    config: &ValidationConfig,
+    ctx: &PositionContext,
+    first_only: bool,
+) where
+    T: GeoFloat + FromPrimitive,
+{
+    let n_problems = out.len();
+    let found = |out: &Vec<ProblemAtPosition>| first_only && out.len() > n_problems;
+    // The ProblemPosition of the problems of the polygon is changed to reflect the MultiPolygon
+    let polygon = &mp.0[j];
+    let polygon_ctx = PositionContext::MultiPolygon(j, ctx);
+    match first_only {
+        true => polygon.collect_first_into(out, config, polygon_ctx),
+        false => polygon.collect_into(out, config, polygon_ctx),
+    }
+    if found(out) || has_unsafe_coordinates(polygon, config) {
+        return;
+    }
+
+    // Special case for MultiPolygon: elements must not overlap and must touch only at points
+    for i in candidates {
+        collect_pair_problems(mp, j, i, out, config, ctx);
+        if found(out) {
+            return;
+        }
+    }
+}
+
+/// Check that the `j`-th element of a MultiPolygon is validly related to the `i`-th element.
+fn pair_is_valid<T: GeoFloat>(
+    mp: &MultiPolygon<T>,
+    j: usize,
+    i: usize,
+    config: &ValidationConfig,
+) -> bool {
+    let (pol, pol2) = (&mp.0[j], &mp.0[i]);
+    // The relation between the elements is symmetric, so it is checked once for each pair
+    if i > j && relation_problem(pol, pol2, i).is_some() {
+        return false;
+    }
+    !(config.check_islands_in_holes && hole_containing(pol2, pol).is_some())
+}
+
+/// Return the problem concerning the relation between two elements of a MultiPolygon
+/// (the `other` one being the second of the pair), if any: only the most specific
+/// problem is returned (identical > overlap > touch on a line).
+fn relation_problem<T: GeoFloat>(
+    pol: &Polygon<T>,
+    pol2: &Polygon<T>,
+    other: usize,
+) -> Option<Problem> {
+    if pol == pol2 {
+        return Some(Problem::ElementsAreIdentical { other });
+    }
+    let im = profile::timed(Check::Relate, || pol.relate(pol2));
+    if im.get(CoordPos::Inside, CoordPos::Inside) == Dimensions::TwoDimensional {
+        Some(Problem::ElementsOverlaps { other })
+    } else if im.get(CoordPos::OnBoundary, CoordPos::OnBoundary) == Dimensions::OneDimensional {
+        Some(Problem::ElementsTouchOnALine { other })
+    } else {
+        None
+    }
+}
+
+/// Push the problems of the `j`-th element of a MultiPolygon concerning its relation
+/// with the `i`-th element.
+fn collect_pair_problems<T: GeoFloat>(
+    mp: &MultiPolygon<T>,
+    j: usize,
+    i: usize,
+    out: &mut Vec<ProblemAtPosition>,
+    config: &ValidationConfig,
+    ctx: &PositionContext,
+) {
+    let polygon = &mp.0[j];
+    let pol2 = &mp.0[i];
+    if has_unsafe_coordinates(pol2, config) {
+        return;
+    }
+    // The relation between both elements is reported once, on the first of them
+    let element_problem = |problem| {
+        ProblemAtPosition(
+            problem,
+            ctx.wrap(ProblemPosition::MultiPolygon(
+                GeometryPosition(j),
+                RingRole::Exterior,
+                CoordinatePosition(None),
+            )),
+        )
+    };
+    // The other problems of identical elements are consequences of this one
+    if i > j {
+        if let Some(problem) = relation_problem(polygon, pol2, i) {
+            let identical = matches!(problem, Problem::ElementsAreIdentical { .. });
+            out.push(element_problem(problem));
+            if identical {
+                return;
             }
         }
-        // Return the reason(s) of invalidity, or None if valid
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
+    } else if polygon == pol2 {
+        return;
+    }
+    // Both polygons declare the same interior ring
+    for (k, other_ring) in shared_holes(polygon, pol2) {
+        out.push(ProblemAtPosition(
+            Problem::SharedHoleBetweenParts {
+                other: i,
+                other_ring,
+            },
+            ctx.wrap(ProblemPosition::MultiPolygon(
+                GeometryPosition(j),
+                RingRole::Interior(k),
+                CoordinatePosition(None),
+            )),
+        ));
+    }
+    if config.check_islands_in_holes {
+        // This polygon lies inside a hole of the other polygon
+        if hole_containing(pol2, polygon).is_some() {
+            out.push(element_problem(Problem::ExteriorRingInsideHole {
+                other: i,
+            }));
+        }
+        // The other polygon lies inside a hole of this polygon
+        if let Some(k) = hole_containing(polygon, pol2) {
+            out.push(ProblemAtPosition(
+                Problem::ExteriorRingInsideHole { other: i },
+                ctx.wrap(ProblemPosition::MultiPolygon(
+                    GeometryPosition(j),
+                    RingRole::Interior(k),
+                    CoordinatePosition(None),
+                )),
+            ));
         }
     }
 }
 
+/// Return, for each element of the MultiPolygon, the (sorted) index of the other elements
+/// whose bounding box intersects its bounding box, found with an R-tree: the elements whose
+/// bounding boxes don't intersect can't overlap, touch or contain each other.
+///
+/// The elements without a finite bounding box (e.g. with non-finite coordinates) are
+/// candidates for all the other elements.
+fn candidate_pairs<T: GeoFloat>(mp: &MultiPolygon<T>) -> Vec<Vec<usize>> {
+    let rects: Vec<Option<Rect<T>>> =
+        mp.0.iter()
+            .map(|polygon| {
+                polygon.bounding_rect().filter(|rect| {
+                    [rect.min(), rect.max()]
+                        .iter()
+                        .all(|c| c.x.is_finite() && c.y.is_finite())
+                })
+            })
+            .collect();
+    let without_rect: Vec<usize> = (0..rects.len()).filter(|&i| rects[i].is_none()).collect();
+
+    let tree = RTree::bulk_load(
+        rects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rect)| {
+                let rect = (*rect)?;
+                Some(GeomWithData::new(
+                    Rectangle::from_corners(rect.min().x_y(), rect.max().x_y()),
+                    i,
+                ))
+            })
+            .collect(),
+    );
+
+    rects
+        .iter()
+        .enumerate()
+        .map(|(j, rect)| {
+            let mut candidates: Vec<usize> = match rect {
+                Some(rect) => tree
+                    .locate_in_envelope_intersecting(&AABB::from_corners(
+                        rect.min().x_y(),
+                        rect.max().x_y(),
+                    ))
+                    .map(|other| other.data)
+                    .chain(without_rect.iter().copied())
+                    .collect(),
+                None => (0..rects.len()).collect(),
+            };
+            candidates.retain(|&i| i != j);
+            candidates.sort_unstable();
+            candidates
+        })
+        .collect()
+}
+
+/// Return the index of the interior rings of `polygon` that are identical to an interior
+/// ring of `other`, along with the index of this interior ring in `other`.
+fn shared_holes<T: GeoFloat>(polygon: &Polygon<T>, other: &Polygon<T>) -> Vec<(usize, usize)> {
+    polygon
+        .interiors()
+        .iter()
+        .enumerate()
+        .filter_map(|(k, hole)| {
+            let other_ring = other.interiors().iter().position(|h| h == hole)?;
+            Some((k, other_ring))
+        })
+        .collect()
+}
+
+/// Return the index of the interior ring of `polygon` that contains the exterior ring of `other`, if any.
+fn hole_containing<T: GeoFloat>(polygon: &Polygon<T>, other: &Polygon<T>) -> Option<usize> {
+    profile::timed(Check::Containment, || {
+        polygon
+            .interiors()
+            .iter()
+            .position(|hole| Polygon::new(hole.clone(), vec![]).contains(other.exterior()))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
-        ProblemReport, RingRole, Valid,
+        ProblemReport, RingRole, Valid, ValidationConfig,
     };
     use geo_types::{LineString, MultiPolygon, Polygon};
     use geos::Geom;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_multipolygon_invalid() {
@@ -172,15 +438,23 @@ mod tests {
                     ProblemPosition::MultiPolygon(
                         GeometryPosition(0),
                         RingRole::Interior(0),
-                        CoordinatePosition(-1)
+                        CoordinatePosition(Some(3))
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::InteriorRingCrossesExterior,
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(0),
+                        RingRole::Interior(0),
+                        CoordinatePosition(None)
                     )
                 ),
                 ProblemAtPosition(
-                    Problem::ElementsAreIdentical,
+                    Problem::ElementsAreIdentical { other: 1 },
                     ProblemPosition::MultiPolygon(
                         GeometryPosition(0),
                         RingRole::Exterior,
-                        CoordinatePosition(-1)
+                        CoordinatePosition(None)
                     )
                 ),
                 ProblemAtPosition(
@@ -188,21 +462,328 @@ mod tests {
                     ProblemPosition::MultiPolygon(
                         GeometryPosition(1),
                         RingRole::Interior(0),
-                        CoordinatePosition(-1)
+                        CoordinatePosition(Some(3))
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::InteriorRingCrossesExterior,
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(1),
+                        RingRole::Interior(0),
+                        CoordinatePosition(None)
+                    )
+                ),
+            ]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
+        assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_multipolygon_island_in_hole() {
+        // The second polygon lies inside the interior ring of the first one
+        let mp = MultiPolygon(vec![
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+                vec![LineString::from(vec![
+                    (2., 2.),
+                    (8., 2.),
+                    (8., 8.),
+                    (2., 8.),
+                    (2., 2.),
+                ])],
+            ),
+            Polygon::new(
+                LineString::from(vec![(4., 4.), (6., 4.), (6., 6.), (4., 6.), (4., 4.)]),
+                vec![],
+            ),
+        ]);
+
+        // This is valid by default
+        assert!(mp.is_valid());
+        assert!(mp.explain_invalidity().is_none());
+
+        // Test that the multipolygon has the same validity status than its GEOS equivalent
+        let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
+        assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
+
+        // But it is reported when the check is enabled
+        let config = ValidationConfig {
+            check_islands_in_holes: true,
+            ..Default::default()
+        };
+        assert!(!mp.is_valid_with(&config));
+        assert_eq!(
+            mp.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::ExteriorRingInsideHole { other: 1 },
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(0),
+                        RingRole::Interior(0),
+                        CoordinatePosition(None)
                     )
                 ),
                 ProblemAtPosition(
-                    Problem::ElementsAreIdentical,
+                    Problem::ExteriorRingInsideHole { other: 0 },
                     ProblemPosition::MultiPolygon(
                         GeometryPosition(1),
                         RingRole::Exterior,
-                        CoordinatePosition(-1)
+                        CoordinatePosition(None)
                     )
                 )
             ]))
         );
+    }
 
-        // Test that the polygon has the same validity status than its GEOS equivalent
+    fn square(x0: f64, y0: f64, x1: f64, y1: f64) -> Polygon {
+        Polygon::new(
+            LineString::from(vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1), (x0, y0)]),
+            vec![],
+        )
+    }
+
+    fn at_exterior(j: usize) -> ProblemPosition {
+        ProblemPosition::MultiPolygon(
+            GeometryPosition(j),
+            RingRole::Exterior,
+            CoordinatePosition(None),
+        )
+    }
+
+    #[test]
+    fn test_multipolygon_many_sparse_elements() {
+        // A grid of 2500 disjoint squares, the last one overlapping the first one
+        let mut polygons: Vec<Polygon> = (0..2500)
+            .map(|n| {
+                let (x, y) = ((n % 50) as f64 * 2., (n / 50) as f64 * 2.);
+                square(x, y, x + 1., y + 1.)
+            })
+            .collect();
+        assert!(MultiPolygon(polygons.clone()).is_valid());
+
+        polygons.push(square(0.5, 0.5, 1.5, 1.5));
+        let mp = MultiPolygon(polygons);
+        assert!(!mp.is_valid());
+        assert_eq!(
+            mp.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::ElementsOverlaps { other: 2500 },
+                at_exterior(0)
+            ),]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_multipolygon_parallel_deterministic() {
+        // A grid of squares, every 7th one overlapping its right neighbour
+        // and every 11th one being self-intersecting
+        let polygons: Vec<Polygon> = (0..1000)
+            .map(|n| {
+                let (x, y) = ((n % 40) as f64 * 2., (n / 40) as f64 * 2.);
+                match n {
+                    n if n % 11 == 0 => Polygon::new(
+                        LineString::from(vec![(x, y), (x + 1., y + 1.), (x + 1., y), (x, y + 1.)]),
+                        vec![],
+                    ),
+                    n if n % 7 == 0 => square(x, y, x + 2.5, y + 1.),
+                    _ => square(x, y, x + 1., y + 1.),
+                }
+            })
+            .collect();
+        let mp = MultiPolygon(polygons);
+        let explain = |threads: usize| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| (mp.is_valid(), mp.explain_invalidity()))
+        };
+        let sequential = explain(1);
+        assert!(!sequential.0);
+        assert!(sequential.1.as_ref().unwrap().len() > 200);
+        for threads in [2, 8] {
+            assert_eq!(explain(threads), sequential);
+        }
+    }
+
+    #[test]
+    fn test_multipolygon_first_problem() {
+        // 400 squares, each one overlapping the next one
+        let mp = MultiPolygon(
+            (0..400)
+                .map(|n| square(n as f64, 0., n as f64 + 2., 2.))
+                .collect(),
+        );
+        assert_eq!(
+            mp.first_problem(),
+            Some(ProblemAtPosition(
+                Problem::ElementsOverlaps { other: 1 },
+                at_exterior(0)
+            ))
+        );
+        assert_eq!(
+            mp.first_problem(),
+            mp.explain_invalidity()
+                .and_then(|report| report.0.into_iter().next())
+        );
+        assert!(square(0., 0., 1., 1.).first_problem().is_none());
+    }
+
+    #[test]
+    fn test_multipolygon_pair_identical() {
+        let mp = MultiPolygon(vec![square(0., 0., 2., 2.), square(0., 0., 2., 2.)]);
+        assert!(!mp.is_valid());
+        assert_eq!(
+            mp.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::ElementsAreIdentical { other: 1 },
+                at_exterior(0)
+            ),]))
+        );
+
+        // Test that the multipolygon has the same validity status than its GEOS equivalent
+        let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
+        assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_multipolygon_f32_pair_identical() {
+        let square = |x0: f32, y0: f32, x1: f32, y1: f32| {
+            Polygon::new(
+                LineString::from(vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1), (x0, y0)]),
+                vec![],
+            )
+        };
+        let mp: MultiPolygon<f32> =
+            MultiPolygon(vec![square(0., 0., 2., 2.), square(0., 0., 2., 2.)]);
+        assert!(!mp.is_valid());
+        assert_eq!(
+            mp.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::ElementsAreIdentical { other: 1 },
+                at_exterior(0)
+            ),]))
+        );
+    }
+
+    #[test]
+    fn test_multipolygon_pair_overlapping() {
+        // The elements overlap and their boundaries share a line
+        let mp = MultiPolygon(vec![square(0., 0., 2., 2.), square(1., 0., 3., 2.)]);
+        assert!(!mp.is_valid());
+        assert_eq!(
+            mp.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::ElementsOverlaps { other: 1 },
+                at_exterior(0)
+            ),]))
+        );
+
+        // Test that the multipolygon has the same validity status than its GEOS equivalent
+        let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
+        assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_multipolygon_pair_touching_on_a_line() {
+        let mp = MultiPolygon(vec![square(0., 0., 2., 2.), square(2., 0., 4., 2.)]);
+        assert!(!mp.is_valid());
+        assert_eq!(
+            mp.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::ElementsTouchOnALine { other: 1 },
+                at_exterior(0)
+            ),]))
+        );
+
+        // Test that the multipolygon has the same validity status than its GEOS equivalent
+        let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
+        assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_multipolygon_pair_touching_at_a_point() {
+        let mp = MultiPolygon(vec![square(0., 0., 2., 2.), square(2., 2., 4., 4.)]);
+        assert!(mp.is_valid());
+        assert!(mp.explain_invalidity().is_none());
+
+        // Test that the multipolygon has the same validity status than its GEOS equivalent
+        let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
+        assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_multipolygon_problem_counts() {
+        // Two identical self-intersecting polygons, and a polygon touching another one on a line
+        let bowtie = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        let mp = MultiPolygon(vec![
+            bowtie.clone(),
+            bowtie,
+            square(10., 0., 12., 2.),
+            square(12., 0., 14., 2.),
+        ]);
+        assert_eq!(
+            mp.problem_counts(),
+            BTreeMap::from([
+                ("elements_are_identical", 1),
+                ("elements_touch_on_a_line", 1),
+                ("self_intersection", 2),
+            ])
+        );
+        assert!(square(0., 0., 1., 1.).problem_counts().is_empty());
+    }
+
+    #[test]
+    fn test_multipolygon_parts_sharing_a_hole() {
+        let hole = LineString::from(vec![(1., 1.), (2., 1.), (2., 2.), (1., 2.), (1., 1.)]);
+        let mp = MultiPolygon(vec![
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (3., 0.), (3., 3.), (0., 3.), (0., 0.)]),
+                vec![hole.clone()],
+            ),
+            Polygon::new(
+                LineString::from(vec![(0.5, 0.5), (4., 0.5), (4., 4.), (0.5, 4.), (0.5, 0.5)]),
+                vec![hole],
+            ),
+        ]);
+        assert!(!mp.is_valid());
+        assert_eq!(
+            mp.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(Problem::ElementsOverlaps { other: 1 }, at_exterior(0)),
+                ProblemAtPosition(
+                    Problem::SharedHoleBetweenParts {
+                        other: 1,
+                        other_ring: 0
+                    },
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(0),
+                        RingRole::Interior(0),
+                        CoordinatePosition(None)
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::SharedHoleBetweenParts {
+                        other: 0,
+                        other_ring: 0
+                    },
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(1),
+                        RingRole::Interior(0),
+                        CoordinatePosition(None)
+                    )
+                ),
+            ]))
+        );
+
+        // Test that the multipolygon has the same validity status than its GEOS equivalent
         let multipolygon_geos: geos::Geometry = (&mp).try_into().unwrap();
         assert_eq!(mp.is_valid(), multipolygon_geos.is_valid());
     }
@@ -0,0 +1,170 @@
+use crate::{
+    utils, CoordAt, CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition,
+    ProblemPosition, Valid,
+};
+use geo_types::{Coord, LineString};
+
+/// The wording used by PostGIS (`ST_IsValidReason`) for each type of problem.
+fn ogc_message(problem: &Problem) -> &'static str {
+    match problem {
+        Problem::NotFinite
+        | Problem::IntroducedNotFinite
+        | Problem::CoordinateMagnitudeUnsafe
+        | Problem::OutOfGeographicBounds => "Invalid Coordinate",
+        Problem::TooFewPoints | Problem::IdenticalCoords | Problem::ZeroArea => {
+            "Too few distinct points in geometry component"
+        }
+        Problem::RingNotClosed => "Ring is not closed",
+        Problem::SelfTouchingRing => "Ring Self-intersection",
+        Problem::InteriorRingNotContainedInExteriorRing | Problem::HoleAreaExceedsShell => {
+            "Hole lies outside shell"
+        }
+        Problem::NestedHoles { .. } => "Holes are nested",
+        Problem::ExteriorRingInsideHole { .. } => "Nested shells",
+        Problem::DisconnectedInterior => "Interior is disconnected",
+        Problem::SharedHoleBetweenParts { .. } => "Duplicate Rings",
+        Problem::CoverageGap
+        | Problem::OutsideMask
+        | Problem::NotConvex
+        | Problem::SignedZero
+        | Problem::IncorrectRingOrientation
+        | Problem::InteriorRingNotAllowed
+        | Problem::HoleRenderedAsIsland
+        | Problem::Custom { .. }
+        | Problem::CollectionTooLarge
+        | Problem::OverlappingEdges { .. }
+        | Problem::RepeatedPoint
+        | Problem::AntimeridianCrossing
+        | Problem::EmptyGeometry
+        | Problem::InvalidRectOrdering => "Topology Validation Error",
+        Problem::CollinearCoords
+        | Problem::SelfIntersection
+        | Problem::Spike
+        | Problem::IntroducedSelfIntersection
+        | Problem::IntersectingRingsOnALine
+        | Problem::IntersectingRingsOnAnArea
+        | Problem::InteriorRingCrossesExterior
+        | Problem::DegenerateHole
+        | Problem::DegeneratePolygon
+        | Problem::ElementsOverlaps { .. }
+        | Problem::ElementsTouchOnALine { .. }
+        | Problem::ElementsAreIdentical { .. } => "Self-intersection",
+    }
+}
+
+/// Return the same position, designating the i-th coordinate of the component
+/// (or None if the position doesn't designate a component made of several coordinates).
+fn with_coordinate(position: &ProblemPosition, i: usize) -> Option<ProblemPosition> {
+    let coord = CoordinatePosition(Some(i));
+    Some(match position {
+        ProblemPosition::Point | ProblemPosition::MultiPoint(_) | ProblemPosition::Whole => {
+            return None
+        }
+        ProblemPosition::Line(_) => ProblemPosition::Line(coord),
+        ProblemPosition::Triangle(_) => ProblemPosition::Triangle(coord),
+        ProblemPosition::Rect(_) => ProblemPosition::Rect(coord),
+        ProblemPosition::LineString(_) => ProblemPosition::LineString(coord),
+        ProblemPosition::MultiLineString(geom, _) => {
+            ProblemPosition::MultiLineString(GeometryPosition(geom.0), coord)
+        }
+        ProblemPosition::Polygon(role, _) => ProblemPosition::Polygon(*role, coord),
+        ProblemPosition::MultiPolygon(geom, role, _) => {
+            ProblemPosition::MultiPolygon(GeometryPosition(geom.0), *role, coord)
+        }
+        ProblemPosition::GeometryCollection(geom, position) => ProblemPosition::GeometryCollection(
+            GeometryPosition(geom.0),
+            Box::new(with_coordinate(position, i)?),
+        ),
+    })
+}
+
+/// Return the coordinates of the component (e.g. the ring) designated by a position.
+pub(crate) fn component_coords<G>(geom: &G, position: &ProblemPosition) -> LineString<f64>
+where
+    G: CoordAt<f64> + ?Sized,
+{
+    LineString(
+        (0..)
+            .map_while(|i| geom.coord_at(&with_coordinate(position, i)?))
+            .collect::<Vec<Coord<f64>>>(),
+    )
+}
+
+/// Return a coordinate locating a problem: the self-intersection point for
+/// self-intersecting rings, otherwise the coordinate designated by its position,
+/// or a coordinate of the component it concerns.
+pub(crate) fn locate_problem<G>(geom: &G, problem: &ProblemAtPosition) -> Option<Coord<f64>>
+where
+    G: CoordAt<f64> + ?Sized,
+{
+    if let Problem::SelfIntersection | Problem::IntroducedSelfIntersection = problem.0 {
+        let coords = component_coords(geom, &problem.1);
+        if let Some(coord) = utils::linestring_self_intersection(&coords) {
+            return Some(coord);
+        }
+    }
+    match geom.coord_at(&problem.1) {
+        Some(coord) => Some(coord),
+        // Locate the problems concerning a whole component
+        None => component_coords(geom, &problem.1).0.first().copied(),
+    }
+}
+
+/// Return the first reason of invalidity of the geometry, worded as by PostGIS.
+pub(crate) fn ogc_reason<G>(geom: &G) -> Option<String>
+where
+    G: Valid + CoordAt<f64> + ?Sized,
+{
+    let report = geom.explain_invalidity()?;
+    let problem = report.0.first()?;
+    let coord = locate_problem(geom, problem);
+    let message = ogc_message(&problem.0);
+    Some(match coord {
+        Some(c) => format!("{}[{} {}]", message, c.x, c.y),
+        None => message.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Valid;
+    use geo_types::{LineString, Point, Polygon};
+
+    #[test]
+    fn test_ogc_reason_self_intersection() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        // SELECT ST_IsValidReason('POLYGON((0 0, 4 0, 0 2, 4 2, 0 0))');
+        assert_eq!(p.ogc_reason(), Some("Self-intersection[2 1]".to_string()));
+    }
+
+    #[test]
+    fn test_ogc_reason_hole_outside_shell() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (5., 1.),
+                (6., 1.),
+                (6., 2.),
+                (5., 2.),
+                (5., 1.),
+            ])],
+        );
+        assert_eq!(
+            p.ogc_reason(),
+            Some("Hole lies outside shell[5 1]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ogc_reason_valid() {
+        assert_eq!(Point::new(1., 2.).ogc_reason(), None);
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(p.ogc_reason(), None);
+    }
+}
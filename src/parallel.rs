@@ -0,0 +1,17 @@
+/// The bound on the type of the coordinates of the MultiPolygons and GeometryCollections,
+/// whose elements are validated concurrently with the `parallel` feature: `Send + Sync`
+/// with this feature (which all the primitive floating point types are), none otherwise.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T: Send + Sync> MaybeSync for T {}
+
+/// The bound on the type of the coordinates of the MultiPolygons and GeometryCollections,
+/// whose elements are validated concurrently with the `parallel` feature: `Send + Sync`
+/// with this feature (which all the primitive floating point types are), none otherwise.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSync for T {}
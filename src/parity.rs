@@ -0,0 +1,54 @@
+use crate::Valid;
+use geos::Geom;
+
+/// Check that this crate and GEOS agree on the validity of a geometry (with the default
+/// ValidationConfig), e.g. to look for divergences over a whole dataset.
+///
+/// The geometries that can't be converted to GEOS geometries (such as LineStrings with
+/// a single coordinate) are considered invalid by GEOS.
+///
+/// Some divergences are intentional:
+/// - a Point with NaN coordinates is an empty Point for GEOS (which is valid),
+///   whereas this crate reports its coordinates as not finite,
+/// - an empty LineString is valid for GEOS, whereas this crate reports it as having
+///   too few points.
+pub fn agrees_with_geos<'a, G>(geom: &'a G) -> bool
+where
+    G: Valid,
+    &'a G: TryInto<geos::Geometry<'static>>,
+{
+    let geos_is_valid = geom
+        .try_into()
+        .map(|geos_geom| geos_geom.is_valid())
+        .unwrap_or(false);
+    geom.is_valid() == geos_is_valid
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agrees_with_geos;
+    use geo_types::{LineString, MultiPolygon, Point, Polygon};
+
+    #[test]
+    fn test_agrees_with_geos() {
+        let square = Polygon::new(
+            LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert!(agrees_with_geos(&square));
+        assert!(agrees_with_geos(&MultiPolygon(vec![
+            square.clone(),
+            square.clone()
+        ])));
+        let bowtie = Polygon::new(
+            LineString::from(vec![(0., 0.), (2., 2.), (2., 0.), (0., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert!(agrees_with_geos(&bowtie));
+        assert!(agrees_with_geos(&LineString::from(vec![(0., 0.)])));
+
+        // The known divergences: GEOS considers these geometries as empty (and valid)
+        assert!(!agrees_with_geos(&Point::new(f64::NAN, f64::NAN)));
+        assert!(!agrees_with_geos(&LineString::<f64>(vec![])));
+    }
+}
@@ -0,0 +1,73 @@
+use geo::BoundingRect;
+use geo_types::{CoordFloat, Rect};
+
+/// A cheap pre-check of a geometry, rejecting some invalid geometries
+/// before running the full validity checks.
+pub trait StructurallyPlausible<T: CoordFloat> {
+    /// Check that the corners of the bounding box of the geometry are finite.
+    ///
+    /// This only requires a single pass over the coordinates and rejects the geometries
+    /// with infinite coordinates or starting with a NaN coordinate (NaN coordinates
+    /// are otherwise ignored when computing the bounding box).
+    /// A geometry passing this check is not necessarily valid
+    /// (but a geometry failing it is invalid).
+    fn is_structurally_plausible(&self) -> bool;
+}
+
+impl<T, G> StructurallyPlausible<T> for G
+where
+    T: CoordFloat,
+    G: BoundingRect<T>,
+{
+    fn is_structurally_plausible(&self) -> bool {
+        let rect: Option<Rect<T>> = self.bounding_rect().into();
+        match rect {
+            // Empty geometries have no bounding box
+            None => true,
+            Some(rect) => {
+                rect.min().x.is_finite()
+                    && rect.min().y.is_finite()
+                    && rect.max().x.is_finite()
+                    && rect.max().y.is_finite()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{StructurallyPlausible, Valid};
+    use geo_types::{Coord, LineString};
+
+    #[test]
+    fn test_structurally_plausible_huge_linestring() {
+        let mut coords: Vec<Coord> = (0..1_000_000)
+            .map(|i| Coord {
+                x: i as f64,
+                y: (i % 2) as f64,
+            })
+            .collect();
+        let ls = LineString(coords.clone());
+        assert!(ls.is_structurally_plausible());
+        assert!(ls.is_valid());
+
+        // Rejected by the bounding box check
+        let mut nan_coords = coords.clone();
+        nan_coords[0].x = f64::NAN;
+        let ls = LineString(nan_coords);
+        assert!(!ls.is_structurally_plausible());
+        assert!(!ls.is_valid());
+
+        let mut inf_coords = coords.clone();
+        inf_coords[500_000].y = f64::INFINITY;
+        let ls = LineString(inf_coords);
+        assert!(!ls.is_structurally_plausible());
+        assert!(!ls.is_valid());
+
+        // A NaN coordinate further in the LineString is only caught by the full check
+        coords[999_999].y = f64::NAN;
+        let ls = LineString(coords);
+        assert!(ls.is_structurally_plausible());
+        assert!(!ls.is_valid());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{ProblemReport, Valid};
+use crate::collect::{CollectProblems, PositionContext};
+use crate::{ProblemAtPosition, ProblemReport, Valid, ValidationConfig};
 use geo::GeoFloat;
 use geo_types::Point;
 
@@ -8,17 +9,33 @@ impl<T> Valid for Point<T>
 where
     T: GeoFloat,
 {
-    fn is_valid(&self) -> bool {
-        self.0.is_valid()
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        self.0.is_valid_with(config)
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        self.0.explain_invalidity()
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        self.0.explain_invalidity_with(config)
+    }
+}
+
+impl<T> CollectProblems for Point<T>
+where
+    T: GeoFloat,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        self.0.collect_into(out, config, ctx)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid};
+    use crate::{
+        InvalidGeometry, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    };
     use geo_types::Point;
     use geos::Geom;
 
@@ -50,4 +67,26 @@ mod tests {
         let pt_geos: geos::Geometry = (&p).try_into().unwrap();
         assert_eq!(p.is_valid(), !pt_geos.is_valid());
     }
+
+    #[test]
+    fn test_point_validate() {
+        fn import(p: Point) -> Result<f64, Box<dyn std::error::Error>> {
+            p.validate()?;
+            Ok(p.x())
+        }
+        assert_eq!(import(Point::new(1., 2.)).unwrap(), 1.);
+
+        let p = Point::new(f64::NAN, 2.);
+        assert_eq!(
+            p.validate(),
+            Err(InvalidGeometry(ProblemReport(vec![ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::Point
+            )])))
+        );
+        assert_eq!(
+            import(p).unwrap_err().to_string(),
+            "Coordinate is not finite (NaN or infinite)"
+        );
+    }
 }
@@ -1,30 +1,88 @@
+use crate::collect::{self, CollectProblems, PositionContext};
+use crate::profile::{self, Check};
 use crate::{
-    utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport,
-    RingRole, Valid,
+    utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemKind, ProblemPosition,
+    ProblemReport, RingOrientation, RingRole, StructurallyPlausible, Valid, ValidationConfig,
 };
-use geo::coordinate_position::CoordPos;
+use geo::coordinate_position::{CoordPos, CoordinatePosition as _};
 use geo::dimensions::Dimensions;
-use geo::{Contains, GeoFloat, Relate};
-use geo_types::Polygon;
+use geo::winding_order::{Winding, WindingOrder};
+use geo::{
+    Area, BooleanOps, Contains, ConvexHull, EuclideanDistance, GeoFloat, Intersects, Relate,
+};
+use geo_types::{Coord, LineString, MultiPolygon, Point, Polygon};
 use num_traits::FromPrimitive;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+use std::collections::{BTreeSet, HashMap};
 
 /// In PostGIS, polygons must follow the following rules to be valid:
 /// - [x] the polygon boundary rings (the exterior shell ring and interior hole rings) are simple (do not cross or self-touch). Because of this a polygon cannnot have cut lines, spikes or loops. This implies that polygon holes must be represented as interior rings, rather than by the exterior ring self-touching (a so-called "inverted hole").
 /// - [x] boundary rings do not cross
 /// - [x] boundary rings may touch at points but only as a tangent (i.e. not in a line)
 /// - [x] interior rings are contained in the exterior ring
-/// - [ ] the polygon interior is simply connected (i.e. the rings must not touch in a way that splits the polygon into more than one part)
+/// - [x] interior rings don't lie entirely on the exterior ring
+/// - [x] interior rings are not nested inside other interior rings
+/// - [x] the polygon interior is simply connected (i.e. the rings must not touch in a way that splits the polygon into more than one part)
+///
+/// The interior rings enclosing more area, in total, than the exterior ring are also reported
+/// (`HoleAreaExceedsShell`), as a cheap sanity check.
+/// The rings enclosing no area (e.g. whose vertices are all collinear) are reported
+/// (`ZeroArea`, see `ValidationConfig::zero_area_tolerance`).
+/// The zero-width spikes of the rings are reported distinctly from their self-intersections
+/// (`Spike`).
+/// Optionally (see `ValidationConfig::ring_closure`), nearly-closed rings are reported.
+/// Optionally (see `ValidationConfig::check_unclosed_rings`), the rings whose first
+/// and last coordinates differ are reported.
+/// Optionally (see `ValidationConfig::check_repeated_points`), the consecutive identical
+/// coordinates of the rings are reported.
+/// Optionally (see `ValidationConfig::check_antimeridian_crossings`), the segments of the
+/// rings spanning more than 180° of longitude are reported.
+/// The coordinates too large for the relations between the rings to be computed reliably
+/// are reported (see `ValidationConfig::max_coordinate_magnitude`).
+/// Optionally (see `ValidationConfig::check_convexity`), non-convex Polygons are reported.
+/// Optionally (see `ValidationConfig::check_triple_touch_points`), the points where two
+/// interior rings and the exterior ring meet are reported.
+/// Optionally (see `ValidationConfig::check_holes_rendered_as_islands`), the interior rings
+/// wound in the same direction as the exterior ring are reported.
+/// Optionally (see `ValidationConfig::ring_orientation`), the rings which don't follow
+/// the chosen winding order are reported.
 impl<T> Valid for Polygon<T>
 where
     T: GeoFloat + FromPrimitive,
 {
-    fn is_valid(&self) -> bool {
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        // Cheap rejection of (most of) the geometries with non-finite coordinates
+        if !self.is_structurally_plausible() {
+            return false;
+        }
         for ring in self.interiors().iter().chain([self.exterior()]) {
             if utils::check_too_few_points(ring, true) {
                 return false;
             }
+            if utils::ring_has_zero_area(ring, config.zero_area_tolerance) {
+                return false;
+            }
+            if utils::check_ring_not_closed(ring, &config.ring_closure)
+                || (config.check_unclosed_rings && !ring.is_closed())
+            {
+                return false;
+            }
+            if config.check_repeated_points && utils::repeated_points(ring).next().is_some() {
+                return false;
+            }
+            if config.check_antimeridian_crossings
+                && utils::antimeridian_crossings(ring).next().is_some()
+            {
+                return false;
+            }
             for coord in ring {
-                if !coord.is_valid() {
+                if !coord.is_valid_with(config)
+                    || utils::check_coord_magnitude_is_unsafe(
+                        coord,
+                        config.max_coordinate_magnitude,
+                    )
+                {
                     return false;
                 }
             }
@@ -33,16 +91,29 @@ where
             }
         }
 
+        // Cheap global sanity check before the relations between the rings
+        if hole_area_exceeds_shell(self) {
+            return false;
+        }
+
         let polygon_exterior = Polygon::new(self.exterior().clone(), vec![]);
+        let pol_interiors = ring_polygons(self.interiors());
+
+        for (interior_ring, pol_interior1) in self.interiors().iter().zip(&pol_interiors) {
+            if !interior_is_contained(&polygon_exterior, interior_ring, config) {
+                return false;
+            }
+
+            // Interior ring and exterior ring must not share a chain of vertices
+            if utils::rings_share_edge(interior_ring, self.exterior()) {
+                return false;
+            }
 
-        for interior_ring in self.interiors() {
-            // geo::contains::Contains return true if the interior
-            // is contained in the exterior even if they touches on one or more points
-            if !polygon_exterior.contains(interior_ring) {
+            if hole_is_degenerate(&polygon_exterior, interior_ring) {
                 return false;
             }
 
-            let im = polygon_exterior.relate(interior_ring);
+            let im = profile::timed(Check::Relate, || polygon_exterior.relate(interior_ring));
 
             // Interior ring and exterior ring may only touch at point (not as a line)
             // and not cross
@@ -53,12 +124,10 @@ where
                 return false;
             }
 
-            let pol_interior1 = Polygon::new(interior_ring.clone(), vec![]);
-
-            for (_i, interior2) in self.interiors().iter().enumerate() {
+            for (interior2, pol_interior2) in self.interiors().iter().zip(&pol_interiors) {
                 if interior_ring != interior2 {
-                    let pol_interior2 = Polygon::new(interior2.clone(), vec![]);
-                    let intersection_matrix = pol_interior1.relate(&pol_interior2);
+                    let intersection_matrix =
+                        profile::timed(Check::Relate, || pol_interior1.relate(pol_interior2));
                     if intersection_matrix.get(CoordPos::Inside, CoordPos::Inside)
                         == Dimensions::TwoDimensional
                     {
@@ -72,118 +141,875 @@ where
                 }
             }
         }
+
+        if disconnecting_ring(self).is_some() {
+            return false;
+        }
+        if config.check_triple_touch_points && !holes_meeting_on_exterior(self).is_empty() {
+            return false;
+        }
+        if config.check_holes_rendered_as_islands
+            && !holes_wound_as_exterior(self, &polygon_exterior, config).is_empty()
+        {
+            return false;
+        }
+        if let Some(orientation) = config.ring_orientation {
+            if !misoriented_rings(self, orientation).is_empty() {
+                return false;
+            }
+        }
+        if config.check_convexity && first_non_convex_vertex(self).is_some() {
+            return false;
+        }
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        collect::quick_check(self)
+    }
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        collect::primary_issue(self)
+    }
+    fn first_problem(&self) -> Option<ProblemAtPosition> {
+        collect::first_problem(self)
+    }
+}
 
-        for (j, ring) in self.interiors().iter().chain([self.exterior()]).enumerate() {
-            // Perform the various checks
-            if utils::check_too_few_points(ring, true) {
-                reason.push(ProblemAtPosition(
-                    Problem::TooFewPoints,
-                    ProblemPosition::Polygon(
-                        if j == 0 {
-                            RingRole::Exterior
-                        } else {
-                            RingRole::Interior(j)
-                        },
-                        CoordinatePosition((ring.0.len() - 2) as isize),
-                    ),
-                ));
-            }
+impl<T> CollectProblems for Polygon<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        collect_polygon_problems(self, out, config, ctx, false);
+    }
 
-            if utils::linestring_has_self_intersection(ring) {
-                reason.push(ProblemAtPosition(
-                    Problem::SelfIntersection,
-                    ProblemPosition::Polygon(
-                        if j == 0 {
-                            RingRole::Exterior
-                        } else {
-                            RingRole::Interior(j)
-                        },
-                        CoordinatePosition(-1),
-                    ),
+    fn collect_first_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        collect_polygon_problems(self, out, config, ctx, true);
+    }
+
+    fn collect_structural_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        collect_ring_problems(self, out, config, &ctx, true);
+    }
+}
+
+/// Push the problems of a Polygon into `out`, stopping as soon as a problem is found
+/// if `first_only` is set (see `CollectProblems::collect_first_into`).
+fn collect_polygon_problems<T>(
+    polygon: &Polygon<T>,
+    out: &mut Vec<ProblemAtPosition>,
+    config: &ValidationConfig,
+    ctx: PositionContext,
+    first_only: bool,
+) where
+    T: GeoFloat + FromPrimitive,
+{
+    let n_problems = out.len();
+    let found = |out: &Vec<ProblemAtPosition>| first_only && out.len() > n_problems;
+    collect_ring_problems(polygon, out, config, &ctx, false);
+    if found(out) {
+        return;
+    }
+
+    // The relations between the rings can't be computed reliably
+    if has_unsafe_coordinates(polygon, config) {
+        return;
+    }
+
+    // The holes can't cover more area than the shell, whatever their relations
+    if hole_area_exceeds_shell(polygon) {
+        out.push(ProblemAtPosition(
+            Problem::HoleAreaExceedsShell,
+            ctx.wrap(ProblemPosition::Polygon(
+                RingRole::Exterior,
+                CoordinatePosition(None),
+            )),
+        ));
+    }
+
+    let polygon_exterior = Polygon::new(polygon.exterior().clone(), vec![]);
+    let pol_interiors = ring_polygons(polygon.interiors());
+
+    for (j, (interior, pol_interior1)) in polygon.interiors().iter().zip(&pol_interiors).enumerate()
+    {
+        if found(out) {
+            return;
+        }
+        // The other problems of such an interior ring (not being contained in the
+        // exterior ring and sharing lines with it) are consequences of this one
+        if hole_covers_exterior(&polygon_exterior, interior, pol_interior1) {
+            out.push(ProblemAtPosition(
+                Problem::DegeneratePolygon,
+                ctx.wrap(ProblemPosition::Polygon(
+                    RingRole::Interior(j),
+                    CoordinatePosition(None),
+                )),
+            ));
+            continue;
+        }
+        if !interior_is_contained(&polygon_exterior, interior, config) {
+            // Point at the first vertex outside of the exterior ring, if any
+            // (the interior ring may be outside only between its vertices)
+            let outside_vertex = first_vertex_outside(&polygon_exterior, interior, config);
+            out.push(ProblemAtPosition(
+                Problem::InteriorRingNotContainedInExteriorRing,
+                ctx.wrap(ProblemPosition::Polygon(
+                    RingRole::Interior(j),
+                    CoordinatePosition(outside_vertex),
+                )),
+            ));
+            // A hole crossing the exterior ring is worse than a hole lying
+            // entirely outside of it, so it is reported distinctly
+            if interior_crosses_exterior(&polygon_exterior, pol_interior1) {
+                out.push(ProblemAtPosition(
+                    Problem::InteriorRingCrossesExterior,
+                    ctx.wrap(ProblemPosition::Polygon(
+                        RingRole::Interior(j),
+                        CoordinatePosition(None),
+                    )),
                 ));
             }
+        }
+
+        // An interior ring lying entirely on the exterior ring has no area
+        if hole_is_degenerate(&polygon_exterior, interior) {
+            out.push(ProblemAtPosition(
+                Problem::DegenerateHole,
+                ctx.wrap(ProblemPosition::Polygon(
+                    RingRole::Interior(j),
+                    CoordinatePosition(None),
+                )),
+            ));
+        }
+
+        let im = profile::timed(Check::Relate, || polygon_exterior.relate(interior));
 
-            for (i, point) in ring.0.iter().enumerate() {
-                if utils::check_coord_is_not_finite(point) {
-                    reason.push(ProblemAtPosition(
-                        Problem::NotFinite,
-                        ProblemPosition::Polygon(
-                            if j == 0 {
-                                RingRole::Exterior
-                            } else {
-                                RingRole::Interior(j)
-                            },
-                            CoordinatePosition(i as isize),
-                        ),
+        // Interior ring and exterior ring may only touch at point (not as a line)
+        // and not cross. A chain of vertices shared by both rings is checked explicitly
+        // (it is a zero-width part of the polygon).
+        if im.get(CoordPos::OnBoundary, CoordPos::Inside) == Dimensions::OneDimensional
+            || utils::rings_share_edge(interior, polygon.exterior())
+        {
+            out.push(ProblemAtPosition(
+                Problem::IntersectingRingsOnALine,
+                ctx.wrap(ProblemPosition::Polygon(
+                    RingRole::Interior(j),
+                    CoordinatePosition(None),
+                )),
+            ));
+        }
+        for (i, (interior2, pol_interior2)) in
+            polygon.interiors().iter().zip(&pol_interiors).enumerate()
+        {
+            if found(out) {
+                return;
+            }
+            if j != i {
+                // A hole inside another hole (an island in a lake) can't be
+                // represented by a single Polygon
+                if profile::timed(Check::Containment, || {
+                    pol_interior1.contains(interior2) || pol_interior2.contains(interior)
+                }) {
+                    out.push(ProblemAtPosition(
+                        Problem::NestedHoles { other: i },
+                        ctx.wrap(ProblemPosition::Polygon(
+                            RingRole::Interior(j),
+                            CoordinatePosition(None),
+                        )),
+                    ));
+                    continue;
+                }
+                let intersection_matrix =
+                    profile::timed(Check::Relate, || pol_interior1.relate(pol_interior2));
+                if intersection_matrix.get(CoordPos::Inside, CoordPos::Inside)
+                    == Dimensions::TwoDimensional
+                {
+                    out.push(ProblemAtPosition(
+                        Problem::IntersectingRingsOnAnArea,
+                        ctx.wrap(ProblemPosition::Polygon(
+                            RingRole::Interior(j),
+                            CoordinatePosition(None),
+                        )),
+                    ));
+                }
+                if intersection_matrix.get(CoordPos::OnBoundary, CoordPos::OnBoundary)
+                    == Dimensions::OneDimensional
+                {
+                    out.push(ProblemAtPosition(
+                        Problem::IntersectingRingsOnALine,
+                        ctx.wrap(ProblemPosition::Polygon(
+                            RingRole::Interior(j),
+                            CoordinatePosition(None),
+                        )),
                     ));
                 }
             }
         }
+    }
+
+    if found(out) {
+        return;
+    }
+
+    // The connectivity of the interior is only meaningful when the rings
+    // touch each other at points only
+    if out.len() == n_problems {
+        if let Some(role) = disconnecting_ring(polygon) {
+            out.push(ProblemAtPosition(
+                Problem::DisconnectedInterior,
+                ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(None))),
+            ));
+        }
+    }
+
+    if config.check_triple_touch_points {
+        for j in holes_meeting_on_exterior(polygon) {
+            out.push(ProblemAtPosition(
+                Problem::DisconnectedInterior,
+                ctx.wrap(ProblemPosition::Polygon(
+                    RingRole::Interior(j),
+                    CoordinatePosition(None),
+                )),
+            ));
+        }
+    }
+
+    if config.check_holes_rendered_as_islands {
+        for j in holes_wound_as_exterior(polygon, &polygon_exterior, config) {
+            out.push(ProblemAtPosition(
+                Problem::HoleRenderedAsIsland,
+                ctx.wrap(ProblemPosition::Polygon(
+                    RingRole::Interior(j),
+                    CoordinatePosition(None),
+                )),
+            ));
+        }
+    }
+
+    if let Some(orientation) = config.ring_orientation {
+        for role in misoriented_rings(polygon, orientation) {
+            out.push(ProblemAtPosition(
+                Problem::IncorrectRingOrientation,
+                ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(None))),
+            ));
+        }
+    }
+
+    if config.check_convexity {
+        if let Some((role, i)) = first_non_convex_vertex(polygon) {
+            out.push(ProblemAtPosition(
+                Problem::NotConvex,
+                ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(i))),
+            ));
+        }
+    }
+}
+
+/// Diagnostic information about a Polygon, to help understanding why it is invalid.
+pub trait PolygonDiagnostics<T: GeoFloat> {
+    /// Return the signed area of each ring of the Polygon (exterior ring first),
+    /// positive for a ring wound counter-clockwise and negative for a ring wound clockwise.
+    fn ring_areas(&self) -> Vec<(RingRole, T)>;
+
+    /// Return, for each interior ring which is not entirely inside the exterior ring,
+    /// the part of the area it encloses that lies outside of the exterior ring
+    /// (i.e. the interior ring minus the exterior ring).
+    /// The interior rings contained in the exterior ring are omitted.
+    fn interiors_outside_exterior(&self) -> Vec<(RingRole, MultiPolygon<T>)>;
+}
+
+impl<T> PolygonDiagnostics<T> for Polygon<T>
+where
+    T: GeoFloat,
+{
+    fn ring_areas(&self) -> Vec<(RingRole, T)> {
+        std::iter::once((RingRole::Exterior, self.exterior()))
+            .chain(
+                self.interiors()
+                    .iter()
+                    .enumerate()
+                    .map(|(j, ring)| (RingRole::Interior(j), ring)),
+            )
+            .map(|(role, ring)| (role, Polygon::new(ring.clone(), vec![]).signed_area()))
+            .collect()
+    }
 
+    fn interiors_outside_exterior(&self) -> Vec<(RingRole, MultiPolygon<T>)> {
         let polygon_exterior = Polygon::new(self.exterior().clone(), vec![]);
+        self.interiors()
+            .iter()
+            .enumerate()
+            .filter_map(|(j, interior)| {
+                let outside = Polygon::new(interior.clone(), vec![]).difference(&polygon_exterior);
+                if outside.unsigned_area() > T::zero() {
+                    Some((RingRole::Interior(j), outside))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
 
-        for (j, interior) in self.interiors().iter().enumerate() {
-            if !polygon_exterior.contains(interior) {
-                reason.push(ProblemAtPosition(
-                    Problem::InteriorRingNotContainedInExteriorRing,
-                    ProblemPosition::Polygon(RingRole::Interior(j), CoordinatePosition(-1)),
+/// Check if the interior rings of a Polygon enclose more area, in total, than its exterior
+/// ring (i.e. if the area of the Polygon, computed as the area of the exterior ring minus
+/// the area of the interior rings, is negative).
+fn hole_area_exceeds_shell<T: GeoFloat>(polygon: &Polygon<T>) -> bool {
+    let ring_area = |ring: &LineString<T>| Polygon::new(ring.clone(), vec![]).unsigned_area();
+    let holes_area = polygon
+        .interiors()
+        .iter()
+        .fold(T::zero(), |area, interior| area + ring_area(interior));
+    ring_area(polygon.exterior()) - holes_area < T::zero()
+}
+
+/// Check if an interior ring is contained in the exterior ring (they may touch on one
+/// or more points), the vertices of the interior ring being allowed to lie outside
+/// of the exterior ring by at most `config.containment_tolerance`.
+fn interior_is_contained<T: GeoFloat>(
+    polygon_exterior: &Polygon<T>,
+    interior: &LineString<T>,
+    config: &ValidationConfig,
+) -> bool {
+    // geo::contains::Contains return true if the interior
+    // is contained in the exterior even if they touches on one or more points
+    if profile::timed(Check::Containment, || polygon_exterior.contains(interior)) {
+        return true;
+    }
+    if config.containment_tolerance == 0. {
+        return false;
+    }
+    first_vertex_outside(polygon_exterior, interior, config).is_none()
+}
+
+/// Return the index of the first vertex of an interior ring lying outside of the exterior
+/// ring (by more than `config.containment_tolerance`).
+fn first_vertex_outside<T: GeoFloat>(
+    polygon_exterior: &Polygon<T>,
+    interior: &LineString<T>,
+    config: &ValidationConfig,
+) -> Option<usize> {
+    let tolerance = T::from(config.containment_tolerance).unwrap();
+    interior.0.iter().position(|coord| {
+        polygon_exterior.coordinate_position(coord) == CoordPos::Outside
+            && Point::from(*coord).euclidean_distance(polygon_exterior.exterior()) > tolerance
+    })
+}
+
+/// Check if an interior ring crosses the exterior ring, i.e. the area it encloses
+/// lies partly inside and partly outside of the exterior ring.
+fn interior_crosses_exterior<T: GeoFloat>(
+    polygon_exterior: &Polygon<T>,
+    pol_interior: &Polygon<T>,
+) -> bool {
+    // The relate matrix of a hole without area is not meaningful here
+    if pol_interior.unsigned_area() == T::zero() {
+        return false;
+    }
+    let im = profile::timed(Check::Relate, || polygon_exterior.relate(pol_interior));
+    im.get(CoordPos::Inside, CoordPos::Inside) == Dimensions::TwoDimensional
+        && im.get(CoordPos::Outside, CoordPos::Inside) == Dimensions::TwoDimensional
+}
+
+/// Push the problems found on each ring of the Polygon (taken separately) into `out`,
+/// skipping the self-intersection check if `structural_only` is set.
+fn collect_ring_problems<T: GeoFloat + FromPrimitive>(
+    polygon: &Polygon<T>,
+    out: &mut Vec<ProblemAtPosition>,
+    config: &ValidationConfig,
+    ctx: &PositionContext,
+    structural_only: bool,
+) {
+    let rings = std::iter::once((RingRole::Exterior, polygon.exterior())).chain(
+        polygon
+            .interiors()
+            .iter()
+            .enumerate()
+            .map(|(j, ring)| (RingRole::Interior(j), ring)),
+    );
+    for (role, ring) in rings {
+        // Perform the various checks
+        if utils::check_too_few_points(ring, true) {
+            out.push(ProblemAtPosition(
+                Problem::TooFewPoints,
+                ctx.wrap(ProblemPosition::Polygon(
+                    role,
+                    CoordinatePosition(Some(ring.0.len() - 2)),
+                )),
+            ));
+        } else if !ring.0.iter().any(utils::check_coord_is_not_finite)
+            && utils::ring_has_zero_area(ring, config.zero_area_tolerance)
+        {
+            out.push(ProblemAtPosition(
+                Problem::ZeroArea,
+                ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(None))),
+            ));
+        }
+
+        let unclosed = config.check_unclosed_rings && !ring.is_closed();
+        if unclosed || utils::check_ring_not_closed(ring, &config.ring_closure) {
+            // Report the closing coordinate that was provided
+            // (or the last coordinate if the ring wasn't closed at all)
+            let position = if unclosed {
+                ring.0.len() - 1
+            } else if utils::ring_closed_at_start(ring) {
+                0
+            } else {
+                ring.0.len() - 2
+            };
+            out.push(ProblemAtPosition(
+                Problem::RingNotClosed,
+                ctx.wrap(ProblemPosition::Polygon(
+                    role,
+                    CoordinatePosition(Some(position)),
+                )),
+            ));
+        }
+
+        if config.check_repeated_points {
+            for i in utils::repeated_points(ring) {
+                out.push(ProblemAtPosition(
+                    Problem::RepeatedPoint,
+                    ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
                 ));
             }
+        }
 
-            let im = polygon_exterior.relate(interior);
-
-            // Interior ring and exterior ring may only touch at point (not as a line)
-            // and not cross
-            if im.get(CoordPos::OnBoundary, CoordPos::Inside) == Dimensions::OneDimensional {
-                reason.push(ProblemAtPosition(
-                    Problem::IntersectingRingsOnALine,
-                    ProblemPosition::Polygon(RingRole::Interior(j), CoordinatePosition(-1)),
+        if config.check_antimeridian_crossings {
+            for i in utils::antimeridian_crossings(ring) {
+                out.push(ProblemAtPosition(
+                    Problem::AntimeridianCrossing,
+                    ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
                 ));
             }
-            let pol_interior1 = Polygon::new(interior.clone(), vec![]);
-            for (i, interior2) in self.interiors().iter().enumerate() {
-                if j != i {
-                    let pol_interior2 = Polygon::new(interior2.clone(), vec![]);
-                    let intersection_matrix = pol_interior1.relate(&pol_interior2);
-                    if intersection_matrix.get(CoordPos::Inside, CoordPos::Inside)
-                        == Dimensions::TwoDimensional
-                    {
-                        reason.push(ProblemAtPosition(
-                            Problem::IntersectingRingsOnAnArea,
-                            ProblemPosition::Polygon(RingRole::Interior(j), CoordinatePosition(-1)),
-                        ));
-                    }
-                    if intersection_matrix.get(CoordPos::OnBoundary, CoordPos::OnBoundary)
-                        == Dimensions::OneDimensional
-                    {
-                        reason.push(ProblemAtPosition(
-                            Problem::IntersectingRingsOnALine,
-                            ProblemPosition::Polygon(RingRole::Interior(j), CoordinatePosition(-1)),
+        }
+
+        if !structural_only {
+            // The spikes are reported distinctly: the self-intersections are looked for
+            // in the ring without its spikes
+            let despiked = remove_spikes(ring);
+            let (checked_ring, original_index) = match &despiked {
+                Some((apexes, despiked_ring, indices)) => {
+                    for &i in apexes {
+                        out.push(ProblemAtPosition(
+                            Problem::Spike,
+                            ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
                         ));
                     }
+                    (despiked_ring, Some(indices))
+                }
+                None => (ring, None),
+            };
+            let original = |i: usize| original_index.map_or(i, |indices| indices[i]);
+
+            if let Some((segment, _)) = utils::self_intersecting_segments(checked_ring) {
+                // Point at the first coordinate of the first segment crossing another segment
+                out.push(ProblemAtPosition(
+                    Problem::SelfIntersection,
+                    ctx.wrap(ProblemPosition::Polygon(
+                        role,
+                        CoordinatePosition(Some(original(segment.index))),
+                    )),
+                ));
+                if let Some(i) = self_touching_vertex(checked_ring) {
+                    out.push(ProblemAtPosition(
+                        Problem::SelfTouchingRing,
+                        ctx.wrap(ProblemPosition::Polygon(
+                            role,
+                            CoordinatePosition(Some(original(i))),
+                        )),
+                    ));
+                }
+            }
+        }
+
+        for (i, point) in ring.0.iter().enumerate() {
+            if utils::check_coord_is_not_finite(point) {
+                out.push(ProblemAtPosition(
+                    Problem::NotFinite,
+                    ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
+                ));
+            } else if utils::check_coord_magnitude_is_unsafe(point, config.max_coordinate_magnitude)
+            {
+                out.push(ProblemAtPosition(
+                    Problem::CoordinateMagnitudeUnsafe,
+                    ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_has_negative_zero(point, config) {
+                out.push(ProblemAtPosition(
+                    Problem::SignedZero,
+                    ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_out_of_geographic_bounds(point, config) {
+                out.push(ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ctx.wrap(ProblemPosition::Polygon(role, CoordinatePosition(Some(i)))),
+                ));
+            }
+        }
+    }
+}
+
+/// Check if a coordinate of the Polygon is not finite or too large for the relations
+/// between its rings (or with other Polygons) to be computed reliably.
+pub(crate) fn has_unsafe_coordinates<T: GeoFloat>(
+    polygon: &Polygon<T>,
+    config: &ValidationConfig,
+) -> bool {
+    std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .flat_map(|ring| ring.0.iter())
+        .any(|coord| {
+            !(coord.x.is_finite() && coord.y.is_finite())
+                || utils::check_coord_magnitude_is_unsafe(coord, config.max_coordinate_magnitude)
+        })
+}
+
+/// Return the ring closing a cycle in the graph linking each ring of the Polygon to the
+/// points where it touches another ring, if any: the rings around such a cycle split the
+/// interior of the Polygon into several parts (e.g. two interior rings touching the exterior
+/// ring and each other at distinct points).
+///
+/// The rings are expected to touch each other at points only (each touch point being
+/// a vertex of at least one of the rings).
+fn disconnecting_ring<T: GeoFloat>(polygon: &Polygon<T>) -> Option<RingRole> {
+    if polygon.interiors().is_empty() {
+        return None;
+    }
+    let rings: Vec<(RingRole, &LineString<T>)> =
+        std::iter::once((RingRole::Exterior, polygon.exterior()))
+            .chain(
+                polygon
+                    .interiors()
+                    .iter()
+                    .enumerate()
+                    .map(|(j, ring)| (RingRole::Interior(j), ring)),
+            )
+            .collect();
+    let finite = |c: &Coord<T>| c.x.is_finite() && c.y.is_finite();
+
+    // The segments of all the rings, to find the other rings each vertex lies on
+    let tree = RTree::bulk_load(
+        rings
+            .iter()
+            .enumerate()
+            .flat_map(|(r, (_, ring))| ring.lines().map(move |line| (r, line)))
+            .filter(|(_, line)| finite(&line.start) && finite(&line.end))
+            .map(|(r, line)| {
+                let rect = Rectangle::from_corners(line.start.x_y(), line.end.x_y());
+                GeomWithData::new(rect, (r, line))
+            })
+            .collect(),
+    );
+
+    // The edges (ring, touch point) of the graph, the touch points being numbered
+    // by the bits of their coordinates
+    let mut points: HashMap<utils::CoordKey, usize> = HashMap::new();
+    let mut edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for (r, (_, ring)) in rings.iter().enumerate() {
+        for c in ring.0.iter().skip(1).filter(|c| finite(c)) {
+            for segment in tree.locate_in_envelope_intersecting(&AABB::from_point(c.x_y())) {
+                let (s, line) = segment.data;
+                if s != r && line.intersects(c) {
+                    let n_points = points.len();
+                    let p = *points.entry(utils::coord_key(c)).or_insert(n_points);
+                    edges.insert((r, p));
+                    edges.insert((s, p));
                 }
             }
         }
+    }
+
+    // Look for a cycle with a union-find over the rings and the touch points
+    let mut parent: Vec<usize> = (0..rings.len() + points.len()).collect();
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+    for (r, p) in edges {
+        let (a, b) = (find(&mut parent, r), find(&mut parent, rings.len() + p));
+        if a == b {
+            return Some(rings[r].0);
+        }
+        parent[a] = b;
+    }
+    None
+}
+
+/// Return the index of the interior rings touching another interior ring at a point
+/// which is also on the exterior ring (such a point can pinch the interior of the Polygon).
+fn holes_meeting_on_exterior<T: GeoFloat>(polygon: &Polygon<T>) -> BTreeSet<usize> {
+    let mut holes = BTreeSet::new();
+    if polygon.interiors().len() < 2 {
+        return holes;
+    }
+    let polygon_exterior = Polygon::new(polygon.exterior().clone(), vec![]);
+    let pol_interiors = ring_polygons(polygon.interiors());
+    // The meeting point is a vertex of at least one of the rings
+    let candidates = polygon
+        .exterior()
+        .0
+        .iter()
+        .chain(
+            polygon
+                .interiors()
+                .iter()
+                .flat_map(|interior| interior.0.iter()),
+        )
+        .filter(|coord| polygon_exterior.coordinate_position(*coord) == CoordPos::OnBoundary);
+    for coord in candidates {
+        let touching = pol_interiors
+            .iter()
+            .enumerate()
+            .filter(|(_, pol_interior)| {
+                pol_interior.coordinate_position(coord) == CoordPos::OnBoundary
+            })
+            .map(|(j, _)| j)
+            .collect::<Vec<_>>();
+        if touching.len() >= 2 {
+            holes.extend(touching);
+        }
+    }
+    holes
+}
+
+/// Check if the path `prev` -> `vertex` -> `next` goes back on itself at `vertex`
+/// (i.e. if `vertex` is the apex of a zero-width spike).
+fn is_spike<T: GeoFloat>(prev: Coord<T>, vertex: Coord<T>, next: Coord<T>) -> bool {
+    let finite = |c: &Coord<T>| !utils::check_coord_is_not_finite(c);
+    if prev == vertex || vertex == next || ![prev, vertex, next].iter().all(finite) {
+        return false;
+    }
+    let (u, v) = (prev - vertex, next - vertex);
+    utils::robust_check_points_are_collinear(&prev, &vertex, &next, 0.)
+        && u.x * v.x + u.y * v.y > T::zero()
+}
+
+/// Return, if a closed ring has spikes, the index of the apex of each spike along with
+/// the ring without its spikes and, for each of its coordinates, the index of the same
+/// coordinate in the original ring.
+///
+/// The spikes are removed repeatedly, so that the spikes uncovered by the removal
+/// of other spikes (e.g. `A B C B A`) are also reported. A ring made only of spikes
+/// (which has no area at all) isn't considered as having spikes.
+fn remove_spikes<T: GeoFloat>(
+    ring: &LineString<T>,
+) -> Option<(Vec<usize>, LineString<T>, Vec<usize>)> {
+    let n = ring.0.len();
+    if n < 4 || !ring.is_closed() {
+        return None;
+    }
+    // The vertices of the ring (without the closing coordinate), with their index
+    let mut vertices: Vec<(usize, Coord<T>)> =
+        ring.0[..n - 1].iter().copied().enumerate().collect();
+    let mut apexes = Vec::new();
+    loop {
+        let m = vertices.len();
+        if m < 3 {
+            break;
+        }
+        let spikes: Vec<usize> = (0..m)
+            .filter(|&k| {
+                is_spike(
+                    vertices[(k + m - 1) % m].1,
+                    vertices[k].1,
+                    vertices[(k + 1) % m].1,
+                )
+            })
+            .collect();
+        if spikes.is_empty() {
+            break;
+        }
+        apexes.extend(spikes.iter().map(|&k| vertices[k].0));
+        // Remove the apexes, then the coordinates repeated by their removal
+        let mut remaining: Vec<(usize, Coord<T>)> = Vec::with_capacity(m);
+        for (k, vertex) in vertices.into_iter().enumerate() {
+            if !spikes.contains(&k) && remaining.last().map(|last| last.1) != Some(vertex.1) {
+                remaining.push(vertex);
+            }
+        }
+        while remaining.len() > 1 && remaining[0].1 == remaining[remaining.len() - 1].1 {
+            remaining.pop();
+        }
+        vertices = remaining;
+    }
+    if apexes.is_empty() || vertices.len() < 3 {
+        return None;
+    }
+    apexes.sort_unstable();
+    vertices.push((n - 1, vertices[0].1));
+    let (indices, coords) = vertices.into_iter().unzip();
+    Some((apexes, LineString(coords), indices))
+}
+
+/// Return the index of the first vertex where a ring touches itself, splitting it into
+/// two loops wound in opposite directions (as when an interior ring is encoded as
+/// a loop of the exterior ring), if any.
+fn self_touching_vertex<T: GeoFloat>(ring: &LineString<T>) -> Option<usize> {
+    let coords = &ring.0;
+    let n = coords.len();
+    for i in 0..n {
+        for j in i + 2..n {
+            // Ignore the closing coordinate
+            if coords[i] != coords[j] || (i == 0 && j == n - 1) {
+                continue;
+            }
+            let loop_area = Polygon::new(LineString(coords[i..=j].to_vec()), vec![]).signed_area();
+            let rest = coords[..=i]
+                .iter()
+                .chain(&coords[j + 1..])
+                .copied()
+                .collect();
+            let rest_area = Polygon::new(LineString(rest), vec![]).signed_area();
+            if loop_area * rest_area < T::zero() {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Return the index of the interior rings contained in the exterior ring and wound in the
+/// same direction as it (many renderers fill them instead of leaving them empty).
+fn holes_wound_as_exterior<T: GeoFloat>(
+    polygon: &Polygon<T>,
+    polygon_exterior: &Polygon<T>,
+    config: &ValidationConfig,
+) -> Vec<usize> {
+    let exterior_winding = match polygon.exterior().winding_order() {
+        Some(winding) => winding,
+        None => return vec![],
+    };
+    polygon
+        .interiors()
+        .iter()
+        .enumerate()
+        .filter(|(_, interior)| {
+            interior.winding_order() == Some(exterior_winding)
+                && interior_is_contained(polygon_exterior, interior, config)
+        })
+        .map(|(j, _)| j)
+        .collect()
+}
 
-        // Return the reason(s) of invalidity, or None if valid
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
+/// Return the rings of the Polygon not wound in the direction required by the given
+/// convention (the rings without a winding order, such as degenerate ones, are ignored).
+fn misoriented_rings<T: GeoFloat>(
+    polygon: &Polygon<T>,
+    orientation: RingOrientation,
+) -> Vec<RingRole> {
+    let (exterior_winding, interior_winding) = match orientation {
+        RingOrientation::ExteriorCounterClockwise => {
+            (WindingOrder::CounterClockwise, WindingOrder::Clockwise)
+        }
+        RingOrientation::ExteriorClockwise => {
+            (WindingOrder::Clockwise, WindingOrder::CounterClockwise)
         }
+    };
+    std::iter::once((RingRole::Exterior, polygon.exterior(), exterior_winding))
+        .chain(
+            polygon
+                .interiors()
+                .iter()
+                .enumerate()
+                .map(|(j, ring)| (RingRole::Interior(j), ring, interior_winding)),
+        )
+        .filter(|(_, ring, expected)| {
+            matches!(ring.winding_order(), Some(winding) if winding != *expected)
+        })
+        .map(|(role, _, _)| role)
+        .collect()
+}
+
+/// Return the first vertex preventing the Polygon from being convex, i.e. the first vertex
+/// of the exterior ring lying inside its convex hull (a reflex vertex), or the first
+/// interior ring if any (without coordinate position).
+fn first_non_convex_vertex<T: GeoFloat>(polygon: &Polygon<T>) -> Option<(RingRole, Option<usize>)> {
+    if !polygon.interiors().is_empty() {
+        return Some((RingRole::Interior(0), None));
     }
+    let hull = polygon.exterior().convex_hull();
+    polygon
+        .exterior()
+        .0
+        .iter()
+        .position(|coord| hull.coordinate_position(coord) == CoordPos::Inside)
+        .map(|i| (RingRole::Exterior, Some(i)))
+}
+
+/// Check if all the vertices of an interior ring (and the middle of all its segments)
+/// are on the boundary of the exterior ring, i.e. the interior ring has no area
+/// relatively to the exterior ring.
+fn hole_is_degenerate<T: GeoFloat>(
+    polygon_exterior: &Polygon<T>,
+    interior: &LineString<T>,
+) -> bool {
+    let two = T::one() + T::one();
+    !interior.0.is_empty()
+        && interior
+            .0
+            .iter()
+            .all(|coord| polygon_exterior.coordinate_position(coord) == CoordPos::OnBoundary)
+        && interior.lines().all(|line| {
+            let middle = Coord {
+                x: (line.start.x + line.end.x) / two,
+                y: (line.start.y + line.end.y) / two,
+            };
+            polygon_exterior.coordinate_position(&middle) == CoordPos::OnBoundary
+        })
+}
+
+/// Check if an interior ring covers the same area as the exterior ring: it lies entirely
+/// on the exterior ring (see `hole_is_degenerate`) but, instead of going back and forth
+/// along it, goes around it.
+fn hole_covers_exterior<T: GeoFloat>(
+    polygon_exterior: &Polygon<T>,
+    interior: &LineString<T>,
+    pol_interior: &Polygon<T>,
+) -> bool {
+    let two = T::one() + T::one();
+    hole_is_degenerate(polygon_exterior, interior)
+        && pol_interior.unsigned_area() * two >= polygon_exterior.unsigned_area()
+}
+
+/// Return each ring as a Polygon without interior rings, built once to be related
+/// to all the other rings.
+fn ring_polygons<T: GeoFloat>(rings: &[LineString<T>]) -> Vec<Polygon<T>> {
+    rings
+        .iter()
+        .map(|ring| Polygon::new(ring.clone(), vec![]))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, RingRole,
-        Valid,
+        CoordinatePosition, PolygonDiagnostics, Problem, ProblemAtPosition, ProblemKind,
+        ProblemPosition, ProblemReport, RingClosure, RingOrientation, RingRole, Simplicity,
+        StrictnessProfile, Valid, ValidationConfig,
     };
+    use geo::Area;
     use geo_types::{Coord, LineString, Polygon};
     use geos::Geom;
 
@@ -276,11 +1102,11 @@ mod tests {
             Some(ProblemReport(vec![
                 ProblemAtPosition(
                     Problem::IntersectingRingsOnALine,
-                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(-1))
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
                 ),
                 ProblemAtPosition(
                     Problem::IntersectingRingsOnALine,
-                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(-1))
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
                 )
             ]))
         );
@@ -315,11 +1141,11 @@ mod tests {
             Some(ProblemReport(vec![
                 ProblemAtPosition(
                     Problem::IntersectingRingsOnAnArea,
-                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(-1))
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
                 ),
                 ProblemAtPosition(
                     Problem::IntersectingRingsOnAnArea,
-                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(-1))
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
                 )
             ]))
         );
@@ -351,7 +1177,7 @@ mod tests {
             p.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::IntersectingRingsOnALine,
-                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(-1))
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
             )]))
         );
 
@@ -374,7 +1200,7 @@ mod tests {
             p.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::TooFewPoints,
-                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(1))
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
             )]))
         );
 
@@ -383,6 +1209,34 @@ mod tests {
         assert_eq!(p.is_valid(), polygon_geos.is_valid());
     }
 
+    #[test]
+    fn test_polygon_overlapping_collinear_segments() {
+        // The segments n°0 and n°3 overlap along (2, 5)-(4, 2), which made the
+        // sweep-line algorithm of geo panic
+        let p = Polygon::new(
+            LineString::from(vec![
+                (2., 5.),
+                (4., 2.),
+                (5., 4.),
+                (1., 1.),
+                (4., 2.),
+                (2., 5.),
+            ]),
+            vec![],
+        );
+        assert!(!p.is_valid());
+        // The ring goes back and forth along this segment
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::Spike,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0)))
+            )]))
+        );
+        assert_eq!(p.primary_issue(), Some(ProblemKind::SelfIntersection));
+        assert!(!p.exterior().is_simple());
+    }
+
     #[test]
     fn test_polygon_invalid_spike() {
         // The following polygon contains a spike
@@ -404,48 +1258,176 @@ mod tests {
         assert_eq!(
             p.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
-                Problem::SelfIntersection,
-                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(-1))
+                Problem::Spike,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(4)))
             )]))
         );
 
         // Test that the polygon has the same validity status than its GEOS equivalent
         let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
         assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // A spike starting at the first coordinate, in a ring also crossing itself
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (-2., 0.),
+                (0., 0.),
+                (4., 0.),
+                (0., 4.),
+                (4., 4.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::Spike,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+                ),
+                ProblemAtPosition(
+                    Problem::SelfIntersection,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(3)))
+                ),
+            ]))
+        );
     }
 
     #[test]
-    fn test_polygon_invalid_exterior_is_not_simple() {
-        // The exterior ring of this polygon is not simple (i.e. it has a self-intersection)
+    fn test_polygon_collinear_ring_has_zero_area() {
+        // The ring has enough points and doesn't cross itself, but its vertices are collinear
         let p = Polygon::new(
-            LineString(vec![
-                Coord { x: 0., y: 0. },
-                Coord { x: 4., y: 0. },
-                Coord { x: 0., y: 2. },
-                Coord { x: 4., y: 2. },
-                Coord { x: 0., y: 0. },
-            ]),
+            LineString::from(vec![(0., 0.), (1., 1.), (2., 2.), (0., 0.)]),
             vec![],
         );
         assert!(!p.is_valid());
         assert_eq!(
             p.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
-                Problem::SelfIntersection,
-                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(-1))
+                Problem::ZeroArea,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None))
             )]))
         );
 
         // Test that the polygon has the same validity status than its GEOS equivalent
         let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
         assert_eq!(p.is_valid(), polygon_geos.is_valid());
-    }
 
-    #[test]
-    fn test_polygon_invalid_interior_not_fully_contained_in_exterior() {
+        // A ring enclosing a tiny area is only reported with a tolerance
         let p = Polygon::new(
-            LineString::from(vec![
-                (0.5, 0.5),
+            LineString::from(vec![(0., 0.), (1., 1.), (2., 2. + 1e-9), (0., 0.)]),
+            vec![],
+        );
+        assert!(p.is_valid());
+        let config = ValidationConfig {
+            zero_area_tolerance: 1e-6,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::ZeroArea,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_polygon_invalid_exterior_is_not_simple() {
+        // The exterior ring of this polygon is not simple (i.e. it has a self-intersection)
+        let p = Polygon::new(
+            LineString(vec![
+                Coord { x: 0., y: 0. },
+                Coord { x: 4., y: 0. },
+                Coord { x: 0., y: 2. },
+                Coord { x: 4., y: 2. },
+                Coord { x: 0., y: 0. },
+            ]),
+            vec![],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+            )]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_invalid_self_touching_exterior_ring() {
+        // The exterior ring forms a bowtie whose lobes (touching at (2, 2))
+        // are wound in opposite directions
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (2., 2.),
+                (4., 4.),
+                (4., 0.),
+                (2., 2.),
+                (0., 4.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::SelfIntersection,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0)))
+                ),
+                ProblemAtPosition(
+                    Problem::SelfTouchingRing,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+                )
+            ]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // A hole encoded as a loop of the exterior ring, touching it at (5, 10)
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (10., 0.),
+                (10., 10.),
+                (5., 10.),
+                (7., 5.),
+                (3., 5.),
+                (5., 10.),
+                (0., 10.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert!(!p.is_valid());
+        assert!(p
+            .explain_invalidity()
+            .unwrap()
+            .0
+            .contains(&ProblemAtPosition(
+                Problem::SelfTouchingRing,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(3)))
+            )));
+    }
+
+    #[test]
+    fn test_polygon_invalid_interior_not_fully_contained_in_exterior() {
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0.5, 0.5),
                 (3., 0.5),
                 (3., 2.5),
                 (0.5, 2.5),
@@ -459,15 +1441,62 @@ mod tests {
                 (1., 1.),
             ])],
         );
+        // The vertex n°3, (3.5, 1), is the first vertex outside of the exterior ring
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::InteriorRingNotContainedInExteriorRing,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(Some(3)))
+                ),
+                ProblemAtPosition(
+                    Problem::InteriorRingCrossesExterior,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+            ]))
+        );
+
+        // The part of the interior ring outside of the exterior ring is a small triangle
+        let outside = p.interiors_outside_exterior();
+        assert_eq!(outside.len(), 1);
+        assert_eq!(outside[0].0, RingRole::Interior(0));
+        let area: f64 = outside[0].1.unsigned_area();
+        assert!((area - 0.125).abs() < 1e-12);
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_invalid_interior_fully_outside_exterior() {
+        // The interior ring doesn't cross the exterior ring, it lies entirely outside of it
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (5., 1.),
+                (6., 1.),
+                (6., 2.),
+                (5., 2.),
+                (5., 1.),
+            ])],
+        );
         assert!(!p.is_valid());
         assert_eq!(
             p.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::InteriorRingNotContainedInExteriorRing,
-                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(-1))
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(Some(0)))
             )]))
         );
 
+        // The whole area enclosed by the interior ring is outside of the exterior ring
+        let outside = p.interiors_outside_exterior();
+        assert_eq!(outside.len(), 1);
+        let area: f64 = outside[0].1.unsigned_area();
+        assert!((area - 1.).abs() < 1e-12);
+
         // Test that the polygon has the same validity status than its GEOS equivalent
         let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
         assert_eq!(p.is_valid(), polygon_geos.is_valid());
@@ -506,12 +1535,12 @@ mod tests {
             p1.explain_invalidity(),
             Some(ProblemReport(vec![
                 ProblemAtPosition(
-                    Problem::IntersectingRingsOnAnArea,
-                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(-1))
+                    Problem::NestedHoles { other: 1 },
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
                 ),
                 ProblemAtPosition(
-                    Problem::IntersectingRingsOnAnArea,
-                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(-1))
+                    Problem::NestedHoles { other: 0 },
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
                 )
             ]))
         );
@@ -525,12 +1554,12 @@ mod tests {
             p2.explain_invalidity(),
             Some(ProblemReport(vec![
                 ProblemAtPosition(
-                    Problem::IntersectingRingsOnAnArea,
-                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(-1))
+                    Problem::NestedHoles { other: 1 },
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
                 ),
                 ProblemAtPosition(
-                    Problem::IntersectingRingsOnAnArea,
-                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(-1))
+                    Problem::NestedHoles { other: 0 },
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
                 )
             ]))
         );
@@ -541,4 +1570,838 @@ mod tests {
         assert_eq!(p1.is_valid(), polygon_geos1.is_valid());
         assert_eq!(p2.is_valid(), polygon_geos2.is_valid());
     }
+
+    #[test]
+    fn test_polygon_invalid_nested_holes_suggests_multipolygon() {
+        // An island (interior ring n°1) in a lake (interior ring n°0)
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(2., 2.), (8., 2.), (8., 8.), (2., 8.), (2., 2.)]),
+                LineString::from(vec![(4., 4.), (6., 4.), (6., 6.), (4., 6.), (4., 4.)]),
+            ],
+        );
+        assert!(!p.is_valid());
+        let report = p.explain_invalidity().unwrap();
+        assert_eq!(
+            report,
+            ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::NestedHoles { other: 1 },
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::NestedHoles { other: 0 },
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
+                )
+            ])
+        );
+        assert!(report.to_string().contains("MultiPolygon"));
+
+        // With a third interior ring, beside the others, the nested rings are still identified
+        let mut interiors = p.interiors().to_vec();
+        interiors.insert(
+            0,
+            LineString::from(vec![(1., 1.), (1.5, 1.), (1.5, 1.5), (1., 1.5), (1., 1.)]),
+        );
+        let p3 = Polygon::new(p.exterior().clone(), interiors);
+        assert_eq!(
+            p3.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::NestedHoles { other: 2 },
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::NestedHoles { other: 1 },
+                    ProblemPosition::Polygon(RingRole::Interior(2), CoordinatePosition(None))
+                )
+            ]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_nearly_closed_ring() {
+        // The last coordinate is very close to the first one but not equal,
+        // so geo_types adds a tiny closing segment
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 1e-9)]),
+            vec![],
+        );
+        let not_closed = Some(ProblemReport(vec![ProblemAtPosition(
+            Problem::RingNotClosed,
+            ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(4))),
+        )]));
+
+        // The ring is accepted as closed by geo_types by default
+        assert!(p.is_valid());
+        assert!(p.explain_invalidity().is_none());
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // It is reported when exact closure is required
+        let exact = ValidationConfig {
+            ring_closure: RingClosure::Exact,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&exact));
+        assert_eq!(p.explain_invalidity_with(&exact), not_closed);
+
+        // It is accepted when the gap is within the snapping tolerance, reported otherwise
+        let snap = ValidationConfig {
+            ring_closure: RingClosure::Snap(1e-6),
+            ..Default::default()
+        };
+        assert!(p.is_valid_with(&snap));
+        assert!(p.explain_invalidity_with(&snap).is_none());
+        let snap = ValidationConfig {
+            ring_closure: RingClosure::Snap(1e-12),
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&snap));
+        assert_eq!(p.explain_invalidity_with(&snap), not_closed);
+
+        // A properly closed ring is never reported
+        let closed = Polygon::new(
+            LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]),
+            vec![],
+        );
+        assert!(closed.is_valid_with(&exact));
+    }
+
+    #[test]
+    fn test_polygon_antimeridian_crossing() {
+        // A small square around the antimeridian, whose vertices were wrapped to [-180, 180]
+        let p = Polygon::new(
+            LineString::from(vec![
+                (179., 0.),
+                (-179., 0.),
+                (-179., 1.),
+                (179., 1.),
+                (179., 0.),
+            ]),
+            vec![],
+        );
+        // This is valid by default (as a strip going around the globe)
+        assert!(p.is_valid());
+
+        let config = ValidationConfig {
+            check_antimeridian_crossings: true,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::AntimeridianCrossing,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0)))
+                ),
+                ProblemAtPosition(
+                    Problem::AntimeridianCrossing,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(2)))
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_polygon_unclosed_ring() {
+        // Deserializing a Polygon doesn't close its rings, as Polygon::new does
+        let p: Polygon = serde_json::from_str(
+            r#"{
+                "exterior": [{"x": 0, "y": 0}, {"x": 4, "y": 0}, {"x": 4, "y": 4}, {"x": 0, "y": 4}],
+                "interiors": []
+            }"#,
+        )
+        .unwrap();
+        assert!(!p.exterior().is_closed());
+
+        // The ring is validated as if it was closed by default
+        assert!(p.is_valid());
+
+        let config = ValidationConfig {
+            check_unclosed_rings: true,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::RingNotClosed,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(3)))
+            )]))
+        );
+
+        // The same ring, closed by Polygon::new
+        let p = Polygon::new(p.exterior().clone(), vec![]);
+        assert!(p.is_valid_with(&config));
+    }
+
+    #[test]
+    fn test_polygon_invalid_interior_ring_shares_vertices_with_exterior_ring() {
+        // The interior ring follows the exterior ring on three consecutive vertices
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (4., 4.),
+                (0., 0.),
+            ])],
+        );
+
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::IntersectingRingsOnALine,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+            )]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_invalid_degenerate_interior_ring() {
+        // The interior ring is traced along the exterior ring (it has no area)
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 0.),
+                (3., 0.),
+                (2., 0.),
+                (1., 0.),
+            ])],
+        );
+
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::ZeroArea,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::InteriorRingNotContainedInExteriorRing,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::DegenerateHole,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::IntersectingRingsOnALine,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+            ]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_interior_ring_identical_to_exterior_ring() {
+        let exterior = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]);
+        let expected = Some(ProblemReport(vec![ProblemAtPosition(
+            Problem::DegeneratePolygon,
+            ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None)),
+        )]));
+
+        let p = Polygon::new(exterior.clone(), vec![exterior.clone()]);
+        assert!(!p.is_valid());
+        assert_eq!(p.explain_invalidity(), expected);
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // The same ring, wound the other way and starting at another vertex
+        let interior = LineString::from(vec![(4., 4.), (4., 0.), (0., 0.), (0., 4.), (4., 4.)]);
+        let p = Polygon::new(exterior, vec![interior]);
+        assert!(!p.is_valid());
+        assert_eq!(p.explain_invalidity(), expected);
+    }
+
+    #[test]
+    fn test_polygon_interior_ring_slightly_outside_exterior_ring() {
+        // One vertex of the interior ring is outside the exterior ring by 1e-10
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (4. + 1e-10, 2.),
+                (1., 3.),
+                (1., 1.),
+            ])],
+        );
+
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::InteriorRingNotContainedInExteriorRing,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(Some(1)))
+                ),
+                ProblemAtPosition(
+                    Problem::InteriorRingCrossesExterior,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+            ]))
+        );
+
+        // The interior ring is accepted with the containment tolerance of the lenient profile
+        let lenient = StrictnessProfile::Lenient.config();
+        assert!(p.is_valid_with(&lenient));
+        assert!(p.explain_invalidity_with(&lenient).is_none());
+    }
+
+    #[test]
+    fn test_polygon_holes_meeting_on_exterior_ring() {
+        // Both interior rings touch the exterior ring (and each other) at (2, 0)
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(2., 0.), (1., 1.), (1., 2.), (2., 0.)]),
+                LineString::from(vec![(2., 0.), (3., 2.), (3., 1.), (2., 0.)]),
+            ],
+        );
+
+        // This is valid by default
+        assert!(p.is_valid());
+        assert!(p.explain_invalidity().is_none());
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // The meeting point is reported when the check is enabled
+        let config = ValidationConfig {
+            check_triple_touch_points: true,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::DisconnectedInterior,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::DisconnectedInterior,
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
+                ),
+            ]))
+        );
+
+        // Interior rings touching each other away from the exterior ring are not reported
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 2.), (2., 1.), (3., 2.), (2., 3.), (1., 2.)]),
+                LineString::from(vec![(3., 2.), (3.5, 1.), (3.75, 2.), (3.5, 3.), (3., 2.)]),
+            ],
+        );
+        assert!(p.is_valid_with(&config));
+    }
+
+    #[test]
+    fn test_polygon_disconnected_interior() {
+        // The interior ring touches the exterior ring at (2, 0) and (2, 4),
+        // splitting the polygon in two parts
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (2., 0.),
+                (3., 2.),
+                (2., 4.),
+                (1., 2.),
+                (2., 0.),
+            ])],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::DisconnectedInterior,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+            )]))
+        );
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // Three interior rings touching each other form a bridge
+        // from the bottom to the top of the exterior ring
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (6., 0.), (6., 6.), (0., 6.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(3., 0.), (4., 1.), (3., 2.), (2., 1.), (3., 0.)]),
+                LineString::from(vec![(3., 2.), (4., 3.), (3., 4.), (2., 3.), (3., 2.)]),
+                LineString::from(vec![(3., 4.), (4., 5.), (3., 6.), (2., 5.), (3., 4.)]),
+            ],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::DisconnectedInterior,
+                ProblemPosition::Polygon(RingRole::Interior(2), CoordinatePosition(None))
+            )]))
+        );
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        // Without the last interior ring, the interior is connected
+        let p = Polygon::new(p.exterior().clone(), p.interiors()[..2].to_vec());
+        assert!(p.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_first_problem() {
+        // The first interior ring lies outside of the exterior ring,
+        // the two other ones overlap each other
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(11., 1.), (12., 1.), (12., 2.), (11., 2.), (11., 1.)]),
+                LineString::from(vec![(1., 1.), (1., 4.), (4., 4.), (4., 1.), (1., 1.)]),
+                LineString::from(vec![(3., 3.), (3., 6.), (6., 6.), (6., 3.), (3., 3.)]),
+            ],
+        );
+        assert_eq!(
+            p.first_problem(),
+            Some(ProblemAtPosition(
+                Problem::InteriorRingNotContainedInExteriorRing,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(Some(0)))
+            ))
+        );
+        assert_eq!(
+            p.first_problem(),
+            p.explain_invalidity()
+                .and_then(|report| report.0.into_iter().next())
+        );
+    }
+
+    #[test]
+    fn test_polygon_quick_check() {
+        // The interior ring has a NaN coordinate: computing the relation between the rings
+        // would panic, so this also checks that the relational checks are skipped
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (1., f64::NAN),
+                (2., 2.),
+                (2., 1.),
+                (1., 1.),
+            ])],
+        );
+        assert_eq!(
+            p.quick_check(),
+            Err(ProblemAtPosition(
+                Problem::NotFinite,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(Some(1)))
+            ))
+        );
+
+        // Without structural problem, the first problem of the relational checks is returned
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(
+            p.quick_check(),
+            Err(ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+            ))
+        );
+
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(p.quick_check(), Ok(()));
+    }
+
+    #[test]
+    fn test_polygon_primary_issue() {
+        // A self-intersecting exterior ring
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (2., 0.),
+                (4., 0.),
+                (0., 2.),
+                (4., 2.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert_eq!(p.primary_issue(), Some(ProblemKind::SelfIntersection));
+
+        // An invalid coordinate is more severe (the relational checks are skipped)
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., f64::NAN), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(p.primary_issue(), Some(ProblemKind::InvalidCoordinate));
+
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(p.primary_issue(), None);
+    }
+
+    #[test]
+    fn test_polygon_coordinate_magnitude_unsafe() {
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (1e300, 0.),
+                (1e300, 1e300),
+                (0., 1.),
+                (0., 0.),
+            ]),
+            vec![LineString::from(vec![
+                (0.2, 0.2),
+                (0.4, 0.2),
+                (0.4, 0.4),
+                (0.2, 0.2),
+            ])],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::CoordinateMagnitudeUnsafe,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+                ),
+                ProblemAtPosition(
+                    Problem::CoordinateMagnitudeUnsafe,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(2)))
+                ),
+            ]))
+        );
+
+        // The threshold can be raised
+        let config = ValidationConfig {
+            max_coordinate_magnitude: f64::MAX,
+            ..Default::default()
+        };
+        assert!(p.is_valid_with(&config));
+    }
+
+    #[test]
+    fn test_polygon_repeated_point() {
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (4., 0.),
+                (4., 4.),
+                (0., 4.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        // This is valid by default
+        assert!(p.is_valid());
+
+        let config = ValidationConfig {
+            check_repeated_points: true,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::RepeatedPoint,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(2)))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_polygon_f32_self_intersection() {
+        let p: Polygon<f32> = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_polygon_f32_coordinate_magnitude_unsafe() {
+        // The orientation determinants of these coordinates overflow f32
+        let p: Polygon<f32> = Polygon::new(
+            LineString::from(vec![(0., 0.), (1e38, 0.), (1e38, 1e38), (0., 1.), (0., 0.)]),
+            vec![],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::CoordinateMagnitudeUnsafe,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(1)))
+                ),
+                ProblemAtPosition(
+                    Problem::CoordinateMagnitudeUnsafe,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(2)))
+                ),
+            ]))
+        );
+
+        // The same coordinates are safe in f64
+        let p: Polygon<f64> = Polygon::new(
+            LineString::from(vec![(0., 0.), (1e38, 0.), (1e38, 1e38), (0., 1.), (0., 0.)]),
+            vec![],
+        );
+        assert!(p.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_hole_area_exceeds_shell() {
+        // Two overlapping holes, covering more area (2 * 64) than the shell (100)
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 1.), (1., 9.), (9., 9.), (9., 1.), (1., 1.)]),
+                LineString::from(vec![
+                    (1.5, 1.5),
+                    (1.5, 9.5),
+                    (9.5, 9.5),
+                    (9.5, 1.5),
+                    (1.5, 1.5),
+                ]),
+            ],
+        );
+        assert!(!p.is_valid());
+        assert_eq!(
+            p.explain_invalidity(),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::HoleAreaExceedsShell,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::IntersectingRingsOnAnArea,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::IntersectingRingsOnAnArea,
+                    ProblemPosition::Polygon(RingRole::Interior(1), CoordinatePosition(None))
+                ),
+            ]))
+        );
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+    }
+
+    #[test]
+    fn test_polygon_hole_rendered_as_island() {
+        // Both rings are wound counter-clockwise
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (2., 2.),
+                (4., 2.),
+                (4., 4.),
+                (2., 4.),
+                (2., 2.),
+            ])],
+        );
+        // This is valid by default
+        assert!(p.is_valid());
+
+        // Test that the polygon has the same validity status than its GEOS equivalent
+        let polygon_geos: geos::Geometry = (&p).try_into().unwrap();
+        assert_eq!(p.is_valid(), polygon_geos.is_valid());
+
+        let config = ValidationConfig {
+            check_holes_rendered_as_islands: true,
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::HoleRenderedAsIsland,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+            )]))
+        );
+        // This is only advisory: the least severe kind of problem
+        assert_eq!(
+            Problem::HoleRenderedAsIsland.kind(),
+            ProblemKind::Convention
+        );
+
+        // The same interior ring wound clockwise is a hole
+        let p = Polygon::new(
+            p.exterior().clone(),
+            vec![LineString::from(vec![
+                (2., 2.),
+                (2., 4.),
+                (4., 4.),
+                (4., 2.),
+                (2., 2.),
+            ])],
+        );
+        assert!(p.is_valid_with(&config));
+    }
+
+    #[test]
+    fn test_polygon_ring_orientation() {
+        // The exterior ring is wound counter-clockwise, the interior ring clockwise
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (2., 2.),
+                (2., 4.),
+                (4., 4.),
+                (4., 2.),
+                (2., 2.),
+            ])],
+        );
+        // Any winding order is accepted by default
+        assert!(p.is_valid());
+
+        let ogc = ValidationConfig {
+            ring_orientation: Some(RingOrientation::ExteriorCounterClockwise),
+            ..Default::default()
+        };
+        assert!(p.is_valid_with(&ogc));
+
+        // Both rings are reported with the opposite convention
+        let shapefile = ValidationConfig {
+            ring_orientation: Some(RingOrientation::ExteriorClockwise),
+            ..Default::default()
+        };
+        assert!(!p.is_valid_with(&shapefile));
+        assert_eq!(
+            p.explain_invalidity_with(&shapefile),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::IncorrectRingOrientation,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None))
+                ),
+                ProblemAtPosition(
+                    Problem::IncorrectRingOrientation,
+                    ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+                ),
+            ]))
+        );
+
+        // Only the interior ring is reported when it is wound as the exterior ring
+        let p = Polygon::new(
+            p.exterior().clone(),
+            vec![LineString::from(vec![
+                (2., 2.),
+                (4., 2.),
+                (4., 4.),
+                (2., 4.),
+                (2., 2.),
+            ])],
+        );
+        assert_eq!(
+            p.explain_invalidity_with(&ogc),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::IncorrectRingOrientation,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_polygon_convexity() {
+        let config = ValidationConfig {
+            check_convexity: true,
+            ..Default::default()
+        };
+
+        // A convex polygon (with a collinear vertex) is accepted
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (1., 0.),
+                (2., 0.),
+                (2., 2.),
+                (0., 2.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert!(p.is_valid_with(&config));
+        assert!(p.explain_invalidity_with(&config).is_none());
+
+        // An L-shaped polygon is reported at its reflex vertex
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (2., 0.),
+                (2., 1.),
+                (1., 1.),
+                (1., 2.),
+                (0., 2.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert!(p.is_valid());
+        assert!(!p.is_valid_with(&config));
+        assert_eq!(
+            p.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::NotConvex,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(3)))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_polygon_ring_areas() {
+        // Counter-clockwise exterior ring, clockwise interior rings
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.), (1., 1.)]),
+                LineString::from(vec![(3., 3.), (3., 3.5), (3.5, 3.5), (3.5, 3.), (3., 3.)]),
+            ],
+        );
+        assert_eq!(
+            p.ring_areas(),
+            vec![
+                (RingRole::Exterior, 16.),
+                (RingRole::Interior(0), -1.),
+                (RingRole::Interior(1), -0.25),
+            ]
+        );
+        assert!(p.interiors_outside_exterior().is_empty());
+    }
 }
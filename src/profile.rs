@@ -0,0 +1,93 @@
+/// The categories of checks whose elapsed time is recorded (with the `profiling` feature).
+#[derive(Clone, Copy)]
+pub(crate) enum Check {
+    SelfIntersection,
+    Relate,
+    Containment,
+}
+
+#[cfg(feature = "profiling")]
+mod enabled {
+    use super::Check;
+    use std::cell::RefCell;
+    use std::time::{Duration, Instant};
+
+    /// The time spent in each category of checks while validating a geometry
+    /// (see `Valid::explain_invalidity_profiled`).
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct ProfileStats {
+        /// Time spent looking for self-intersections in rings and LineStrings.
+        pub self_intersection: Duration,
+        /// Time spent computing DE-9IM matrices between rings or elements.
+        pub relate: Duration,
+        /// Time spent checking that rings or elements contain each other.
+        pub containment: Duration,
+    }
+
+    thread_local! {
+        static STATS: RefCell<Option<ProfileStats>> = const { RefCell::new(None) };
+    }
+
+    /// Run a check, adding its elapsed time to its category if the time is being recorded.
+    pub(crate) fn timed<R>(check: Check, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+        STATS.with(|stats| {
+            if let Some(stats) = stats.borrow_mut().as_mut() {
+                *match check {
+                    Check::SelfIntersection => &mut stats.self_intersection,
+                    Check::Relate => &mut stats.relate,
+                    Check::Containment => &mut stats.containment,
+                } += elapsed;
+            }
+        });
+        result
+    }
+
+    /// Run `f`, returning its result and the time spent in each category of checks.
+    pub(crate) fn record<R>(f: impl FnOnce() -> R) -> (R, ProfileStats) {
+        let previous = STATS.with(|stats| stats.replace(Some(ProfileStats::default())));
+        let result = f();
+        let recorded = STATS.with(|stats| stats.replace(previous));
+        (result, recorded.unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "profiling")]
+pub(crate) use enabled::record;
+#[cfg(feature = "profiling")]
+pub(crate) use enabled::timed;
+#[cfg(feature = "profiling")]
+pub use enabled::ProfileStats;
+
+/// Run a check (its elapsed time is only recorded with the `profiling` feature).
+#[cfg(not(feature = "profiling"))]
+#[inline(always)]
+pub(crate) fn timed<R>(_check: Check, f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+#[cfg(all(test, feature = "profiling"))]
+mod tests {
+    use crate::Valid;
+    use geo_types::{LineString, Polygon};
+    use std::time::Duration;
+
+    #[test]
+    fn test_explain_invalidity_profiled() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![
+                LineString::from(vec![(1., 1.), (1., 4.), (4., 4.), (4., 1.), (1., 1.)]),
+                LineString::from(vec![(3., 3.), (3., 6.), (6., 6.), (6., 3.), (3., 3.)]),
+            ],
+        );
+        let (problems, stats) = p.explain_invalidity_profiled();
+        assert_eq!(problems, p.explain_invalidity().map(|report| report.0));
+        assert!(problems.is_some());
+        assert!(stats.self_intersection > Duration::ZERO);
+        assert!(stats.relate > Duration::ZERO);
+        assert!(stats.containment > Duration::ZERO);
+    }
+}
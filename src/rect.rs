@@ -1,37 +1,156 @@
+use crate::collect::{self, CollectProblems, PositionContext};
 use crate::{
-    utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    utils, CoordinatePosition, LineDegeneracy, Problem, ProblemAtPosition, ProblemPosition,
+    ProblemReport, Valid, ValidationConfig,
 };
+use geo::GeoFloat;
 use geo_types::Rect;
+use num_traits::FromPrimitive;
 
-impl Valid for Rect {
-    fn is_valid(&self) -> bool {
+/// A Rect must have a non-zero width and height, otherwise some of its sides
+/// are degenerate (and its conversion to a Polygon is invalid).
+///
+/// Its minimum corner must not lie beyond its maximum corner (`InvalidRectOrdering`).
+/// This is guaranteed by the constructors of geo-types, but not when it is deserialized.
+impl<T> Valid for Rect<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
         if utils::check_coord_is_not_finite(&self.min())
             || utils::check_coord_is_not_finite(&self.max())
         {
             return false;
         }
+        if [self.min(), self.max()].iter().any(|coord| {
+            utils::check_coord_has_negative_zero(coord, config)
+                || utils::check_coord_out_of_geographic_bounds(coord, config)
+        }) {
+            return false;
+        }
+        if has_inverted_corners(self) {
+            return false;
+        }
+        if has_degenerate_side(self, config) {
+            return false;
+        }
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
 
+impl<T> CollectProblems for Rect<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
         if utils::check_coord_is_not_finite(&self.min()) {
-            reason.push(ProblemAtPosition(
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Rect(CoordinatePosition(0)),
+                ctx.wrap(ProblemPosition::Rect(CoordinatePosition(Some(0)))),
             ));
         }
         if utils::check_coord_is_not_finite(&self.max()) {
-            reason.push(ProblemAtPosition(
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Rect(CoordinatePosition(1)),
+                ctx.wrap(ProblemPosition::Rect(CoordinatePosition(Some(1)))),
             ));
         }
-
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
+        for (i, coord) in [self.min(), self.max()].iter().enumerate() {
+            if utils::check_coord_has_negative_zero(coord, config) {
+                out.push(ProblemAtPosition(
+                    Problem::SignedZero,
+                    ctx.wrap(ProblemPosition::Rect(CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_out_of_geographic_bounds(coord, config) {
+                out.push(ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ctx.wrap(ProblemPosition::Rect(CoordinatePosition(Some(i)))),
+                ));
+            }
+        }
+        if has_inverted_corners(self) {
+            out.push(ProblemAtPosition(
+                Problem::InvalidRectOrdering,
+                ctx.wrap(ProblemPosition::Rect(CoordinatePosition(Some(0)))),
+            ));
         }
+        if has_degenerate_side(self, config) {
+            out.push(ProblemAtPosition(
+                Problem::IdenticalCoords,
+                ctx.wrap(ProblemPosition::Rect(CoordinatePosition(Some(0)))),
+            ));
+        }
+    }
+}
+
+fn has_inverted_corners<T: GeoFloat>(rect: &Rect<T>) -> bool {
+    rect.min().x > rect.max().x || rect.min().y > rect.max().y
+}
+
+fn has_degenerate_side<T: GeoFloat>(rect: &Rect<T>, config: &ValidationConfig) -> bool {
+    rect.to_lines()
+        .iter()
+        .any(|line| line.is_degenerate(config.near_duplicate_tolerance))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    };
+    use geo_types::{coord, Polygon, Rect};
+
+    #[test]
+    fn test_rect_valid() {
+        let r = Rect::new(coord! { x: 0., y: 0. }, coord! { x: 1., y: 2. });
+        assert!(r.is_valid());
+        assert!(r.explain_invalidity().is_none());
+    }
+
+    #[test]
+    fn test_rect_zero_width() {
+        let r = Rect::new(coord! { x: 1., y: 0. }, coord! { x: 1., y: 2. });
+        assert!(!r.is_valid());
+        assert_eq!(
+            r.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::IdenticalCoords,
+                ProblemPosition::Rect(CoordinatePosition(Some(0)))
+            )]))
+        );
+        // Its conversion to a Polygon is invalid too
+        assert!(!Polygon::from(r).is_valid());
+
+        // Test that the rect has the same validity status than its GEOS equivalent
+        let rect_geos: geos::Geometry = (&Polygon::from(r)).try_into().unwrap();
+        assert_eq!(r.is_valid(), rect_geos.is_valid());
+    }
+
+    #[test]
+    fn test_rect_inverted() {
+        // The constructors of geo-types reorder the corners, but deserialization doesn't
+        let r: Rect =
+            serde_json::from_str(r#"{"min":{"x":2.0,"y":0.0},"max":{"x":1.0,"y":2.0}}"#).unwrap();
+        assert!(!r.is_valid());
+        assert_eq!(
+            r.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::InvalidRectOrdering,
+                ProblemPosition::Rect(CoordinatePosition(Some(0)))
+            )]))
+        );
+
+        let r: Rect =
+            serde_json::from_str(r#"{"min":{"x":1.0,"y":0.0},"max":{"x":2.0,"y":2.0}}"#).unwrap();
+        assert!(r.is_valid());
     }
 }
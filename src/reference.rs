@@ -0,0 +1,58 @@
+use crate::{ProblemAtPosition, ProblemFlags, ProblemKind, ProblemReport, Valid, ValidationConfig};
+use std::collections::BTreeMap;
+
+/// A reference is valid if the geometry it refers to is valid.
+impl<T> Valid for &T
+where
+    T: Valid + ?Sized,
+{
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        (**self).is_valid_with(config)
+    }
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        (**self).explain_invalidity_with(config)
+    }
+    fn quick_check(&self) -> Result<(), ProblemAtPosition> {
+        (**self).quick_check()
+    }
+    fn primary_issue(&self) -> Option<ProblemKind> {
+        (**self).primary_issue()
+    }
+    fn first_problem(&self) -> Option<ProblemAtPosition> {
+        (**self).first_problem()
+    }
+    fn problem_flags(&self) -> ProblemFlags {
+        (**self).problem_flags()
+    }
+    fn problem_counts(&self) -> BTreeMap<&'static str, usize> {
+        (**self).problem_counts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Valid;
+    use geo_types::{Geometry, LineString, Polygon};
+
+    #[test]
+    fn test_references() {
+        fn count_invalid<G: Valid>(geoms: impl IntoIterator<Item = G>) -> usize {
+            geoms.into_iter().filter(|g| !g.is_valid()).count()
+        }
+        let geoms = vec![
+            Geometry::Polygon(Polygon::new(
+                LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+                vec![],
+            )),
+            Geometry::Polygon(Polygon::new(
+                LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]),
+                vec![],
+            )),
+        ];
+        assert_eq!(count_invalid(&geoms), 1);
+        assert_eq!(
+            (&&geoms[0]).explain_invalidity(),
+            geoms[0].explain_invalidity()
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use geo::GeoFloat;
+use geo_types::{Coord, LineString, MultiPolygon, Polygon};
+
+/// A trait to repair the Polygons (and MultiPolygons) made invalid by imprecise coordinates.
+pub trait SnapRepair {
+    /// Return a repaired copy of the geometry:
+    /// - each vertex within `tolerance` of a vertex met before (in any ring) is snapped to
+    ///   it, which resolves near-duplicates, rings not closed by a tiny gap and
+    ///   micro self-intersections between vertices,
+    /// - the repeated consecutive vertices are removed,
+    /// - the vertices within `tolerance` of the line joining their neighbours
+    ///   (near-collinear vertices) are removed,
+    /// - the rings are closed, and the interior rings left with too few points are removed.
+    ///
+    /// Unlike the other methods of this crate, this changes the coordinates of the geometry:
+    /// `tolerance` should stay well below the size of the details to preserve.
+    /// The non-finite coordinates are left as is, and the result is not guaranteed
+    /// to be valid (e.g. when rings cross each other far from any vertex).
+    fn make_valid_snapped(&self, tolerance: f64) -> Self;
+}
+
+impl<T: GeoFloat> SnapRepair for Polygon<T> {
+    fn make_valid_snapped(&self, tolerance: f64) -> Self {
+        snap_polygon(self, &mut Vec::new(), T::from(tolerance).unwrap())
+    }
+}
+
+impl<T: GeoFloat> SnapRepair for MultiPolygon<T> {
+    fn make_valid_snapped(&self, tolerance: f64) -> Self {
+        let tolerance = T::from(tolerance).unwrap();
+        let mut anchors = Vec::new();
+        MultiPolygon(
+            self.0
+                .iter()
+                .map(|polygon| snap_polygon(polygon, &mut anchors, tolerance))
+                .collect(),
+        )
+    }
+}
+
+/// Snap the rings of a Polygon to the `anchors` (the vertices met before),
+/// the vertices of the Polygon being added to them.
+fn snap_polygon<T: GeoFloat>(
+    polygon: &Polygon<T>,
+    anchors: &mut Vec<Coord<T>>,
+    tolerance: T,
+) -> Polygon<T> {
+    let exterior = snap_ring(polygon.exterior(), anchors, tolerance);
+    let interiors = polygon
+        .interiors()
+        .iter()
+        .map(|interior| snap_ring(interior, anchors, tolerance))
+        .filter(|interior| interior.0.len() >= 4)
+        .collect();
+    Polygon::new(exterior, interiors)
+}
+
+fn snap_ring<T: GeoFloat>(
+    ring: &LineString<T>,
+    anchors: &mut Vec<Coord<T>>,
+    tolerance: T,
+) -> LineString<T> {
+    let mut coords: Vec<Coord<T>> = Vec::with_capacity(ring.0.len());
+    for c in ring.0.iter() {
+        let snapped = snap_coord(*c, anchors, tolerance);
+        if coords.last() != Some(&snapped) {
+            coords.push(snapped);
+        }
+    }
+    // Work on the open ring, to handle its closing vertex as the other ones
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+
+    // Remove the near-collinear vertices, until there is none left
+    let mut i = 0;
+    while coords.len() > 3 && i < coords.len() {
+        let n = coords.len();
+        let (prev, c, next) = (coords[(i + n - 1) % n], coords[i], coords[(i + 1) % n]);
+        if distance_to_line(c, prev, next) <= tolerance {
+            coords.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+
+    let mut ring = LineString(coords);
+    ring.close();
+    ring
+}
+
+/// Return the first anchor within `tolerance` of `c` (or `c` itself, which becomes an anchor).
+fn snap_coord<T: GeoFloat>(c: Coord<T>, anchors: &mut Vec<Coord<T>>, tolerance: T) -> Coord<T> {
+    if !c.x.is_finite() || !c.y.is_finite() {
+        return c;
+    }
+    match anchors
+        .iter()
+        .find(|anchor| (anchor.x - c.x).hypot(anchor.y - c.y) <= tolerance)
+    {
+        Some(anchor) => *anchor,
+        None => {
+            anchors.push(c);
+            c
+        }
+    }
+}
+
+/// Return the distance from `c` to the (infinite) line passing through `a` and `b`.
+fn distance_to_line<T: GeoFloat>(c: Coord<T>, a: Coord<T>, b: Coord<T>) -> T {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length = dx.hypot(dy);
+    if length == T::zero() {
+        return (c.x - a.x).hypot(c.y - a.y);
+    }
+    ((c.x - a.x) * dy - (c.y - a.y) * dx).abs() / length
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SnapRepair, StrictnessProfile, Valid};
+    use geo_types::{LineString, Polygon};
+
+    #[test]
+    fn test_make_valid_snapped_micro_gap() {
+        // The exterior ring is closed by geo-types, leaving a gap of 1e-9
+        // between its last two coordinates, and has a near-collinear vertex
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (5., 1e-9),
+                (10., 0.),
+                (10., 10.),
+                (0., 10.),
+                (1e-9, 0.),
+            ]),
+            vec![LineString::from(vec![
+                (2., 2.),
+                (2., 4.),
+                (4., 4.),
+                (4., 2.),
+                (4., 2. + 1e-9),
+            ])],
+        );
+        let config = StrictnessProfile::Strict.config();
+        assert!(!p.is_valid_with(&config));
+
+        let repaired = p.make_valid_snapped(1e-6);
+        assert!(repaired.is_valid_with(&config));
+        assert_eq!(
+            repaired,
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+                vec![LineString::from(vec![
+                    (2., 2.),
+                    (2., 4.),
+                    (4., 4.),
+                    (4., 2.),
+                    (2., 2.)
+                ])],
+            )
+        );
+
+        // The coordinates of a valid polygon are kept
+        assert_eq!(repaired.make_valid_snapped(1e-6), repaired);
+    }
+}
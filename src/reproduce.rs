@@ -0,0 +1,119 @@
+use crate::ogc::component_coords;
+use crate::{
+    utils, CoordAt, CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition,
+    ProblemPosition, RingRole, Valid,
+};
+use geo_types::{Geometry, LineString, MultiLineString, Point, Polygon};
+
+/// Return the same position, designating the ring with the given role of the same Polygon
+/// (or None if the position doesn't designate a ring).
+fn with_ring_role(position: &ProblemPosition, role: RingRole) -> Option<ProblemPosition> {
+    Some(match position {
+        ProblemPosition::Polygon(_, coord) => {
+            ProblemPosition::Polygon(role, CoordinatePosition(coord.0))
+        }
+        ProblemPosition::MultiPolygon(geom, _, coord) => ProblemPosition::MultiPolygon(
+            GeometryPosition(geom.0),
+            role,
+            CoordinatePosition(coord.0),
+        ),
+        ProblemPosition::GeometryCollection(geom, position) => ProblemPosition::GeometryCollection(
+            GeometryPosition(geom.0),
+            Box::new(with_ring_role(position, role)?),
+        ),
+        _ => return None,
+    })
+}
+
+/// Return the smallest geometry reproducing the first problem of a geometry
+/// (see `Valid::minimal_failing_subgeometry`).
+pub(crate) fn minimal_failing_subgeometry<G>(geom: &G) -> Option<Geometry>
+where
+    G: Valid + CoordAt<f64> + ?Sized,
+{
+    let report = geom.explain_invalidity()?;
+    let ProblemAtPosition(problem, position) = report.0.first()?;
+    let component = component_coords(geom, position);
+    match problem {
+        Problem::SelfIntersection => {
+            if let Some((segment, other)) = utils::self_intersecting_segments(&component) {
+                return Some(Geometry::MultiLineString(MultiLineString(vec![
+                    LineString(vec![segment.line.start, segment.line.end]),
+                    LineString(vec![other.line.start, other.line.end]),
+                ])));
+            }
+        }
+        Problem::InteriorRingNotContainedInExteriorRing | Problem::InteriorRingCrossesExterior => {
+            if let Some(exterior) = with_ring_role(position, RingRole::Exterior) {
+                return Some(Geometry::Polygon(Polygon::new(
+                    component_coords(geom, &exterior),
+                    vec![component],
+                )));
+            }
+        }
+        _ => {}
+    }
+    if !component.0.is_empty() {
+        return Some(Geometry::LineString(component));
+    }
+    geom.coord_at(position)
+        .map(|coord| Geometry::Point(Point(coord)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Valid;
+    use geo_types::{Geometry, LineString, MultiLineString, MultiPolygon, Polygon};
+
+    #[test]
+    fn test_minimal_failing_subgeometry_self_intersection() {
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (4., 1.),
+                (0., 3.),
+                (4., 3.),
+                (4., 4.),
+                (0., 4.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        // The segments from (4, 1) to (0, 3) and from (4, 4) to (0, 4)
+        // don't intersect, only the ones crossing the ring do
+        assert_eq!(
+            p.minimal_failing_subgeometry(),
+            Some(Geometry::MultiLineString(MultiLineString(vec![
+                LineString::from(vec![(4., 1.), (0., 3.)]),
+                LineString::from(vec![(0., 4.), (0., 0.)]),
+            ])))
+        );
+        let valid = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(valid.minimal_failing_subgeometry(), None);
+    }
+
+    #[test]
+    fn test_minimal_failing_subgeometry_hole_outside_shell() {
+        let exterior = LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]);
+        let hole_inside = LineString::from(vec![(1., 1.), (1., 2.), (2., 2.), (2., 1.), (1., 1.)]);
+        let hole_outside = LineString::from(vec![(5., 1.), (5., 2.), (6., 2.), (6., 1.), (5., 1.)]);
+        let mp = MultiPolygon(vec![
+            Polygon::new(
+                LineString::from(vec![(10., 0.), (14., 0.), (14., 4.), (10., 4.), (10., 0.)]),
+                vec![],
+            ),
+            Polygon::new(exterior.clone(), vec![hole_inside, hole_outside.clone()]),
+        ]);
+        assert_eq!(
+            mp.minimal_failing_subgeometry(),
+            Some(Geometry::Polygon(Polygon::new(
+                exterior,
+                vec![hole_outside]
+            )))
+        );
+    }
+}
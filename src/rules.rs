@@ -0,0 +1,111 @@
+use crate::{ProblemAtPosition, Valid};
+use geo_types::Geometry;
+
+/// A user-provided validity rule (e.g. "no vertex within 1m of a protected feature"),
+/// checked along with the built-in checks by `Valid::explain_invalidity_with_rules`.
+///
+/// The problems found by a rule are usually reported as `Problem::Custom`, at a position
+/// in the geometry given to `check`.
+pub trait CustomRule {
+    /// Return the problems found in the geometry (an empty vector if it follows the rule).
+    fn check(&self, geom: &Geometry) -> Vec<ProblemAtPosition>;
+}
+
+/// Return the problems found by the built-in checks (with the default ValidationConfig)
+/// followed by the ones found by each rule, in order, or None if there is none.
+pub(crate) fn explain_invalidity_with_rules<G>(
+    geom: &G,
+    rules: &[Box<dyn CustomRule>],
+) -> Option<Vec<ProblemAtPosition>>
+where
+    G: Valid + Clone + Into<Geometry>,
+{
+    let mut problems = geom
+        .explain_invalidity()
+        .map(|report| report.0)
+        .unwrap_or_default();
+    if !rules.is_empty() {
+        let geometry: Geometry = geom.clone().into();
+        for rule in rules {
+            problems.extend(rule.check(&geometry));
+        }
+    }
+    if problems.is_empty() {
+        None
+    } else {
+        Some(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CoordinatePosition, CustomRule, Problem, ProblemAtPosition, ProblemPosition, RingRole,
+        Valid,
+    };
+    use geo::CoordsIter;
+    use geo_types::{coord, Coord, Geometry, LineString, Polygon};
+
+    /// Flag the vertices of a Polygon exterior ring lying on a protected location.
+    struct ProtectedVertex(Coord);
+
+    impl CustomRule for ProtectedVertex {
+        fn check(&self, geom: &Geometry) -> Vec<ProblemAtPosition> {
+            match geom {
+                Geometry::Polygon(p) => p
+                    .exterior()
+                    .coords_iter()
+                    .enumerate()
+                    .filter(|(_, c)| *c == self.0)
+                    .map(|(i, _)| {
+                        ProblemAtPosition(
+                            Problem::Custom {
+                                code: "protected_vertex".to_string(),
+                            },
+                            ProblemPosition::Polygon(
+                                RingRole::Exterior,
+                                CoordinatePosition(Some(i)),
+                            ),
+                        )
+                    })
+                    .collect(),
+                _ => vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain_invalidity_with_rules() {
+        let rules: Vec<Box<dyn CustomRule>> =
+            vec![Box::new(ProtectedVertex(coord! { x: 4., y: 4. }))];
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![],
+        );
+        assert!(p.is_valid());
+        assert_eq!(
+            p.explain_invalidity_with_rules(&rules),
+            Some(vec![ProblemAtPosition(
+                Problem::Custom {
+                    code: "protected_vertex".to_string()
+                },
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(2)))
+            )])
+        );
+        assert_eq!(p.explain_invalidity_with_rules(&[]), None);
+
+        // The built-in problems come first
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 4.), (4., 4.), (0., 0.)]),
+            vec![],
+        );
+        let problems = p.explain_invalidity_with_rules(&rules).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].0, Problem::SelfIntersection);
+        assert_eq!(problems[1].0.code(), "custom");
+        assert_eq!(
+            problems[1].1,
+            ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(3)))
+        );
+    }
+}
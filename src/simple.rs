@@ -0,0 +1,139 @@
+use crate::{
+    utils, CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
+    ProblemReport,
+};
+use geo::GeoFloat;
+use geo_types::{LineString, MultiLineString};
+
+/// Check the simplicity of linear geometries, as defined by OGC Simple Features:
+/// a simple LineString doesn't cross or touch itself (except at its endpoints when
+/// it is closed).
+///
+/// Simplicity is distinct from validity: a self-crossing LineString is valid (see the
+/// Valid trait), but not simple.
+pub trait Simplicity {
+    /// Check if the geometry is simple.
+    fn is_simple(&self) -> bool;
+    /// Return the reasons why the geometry is not simple (`SelfIntersection`, reported for
+    /// each self-intersecting LineString at the first coordinate of the first segment
+    /// crossing another segment), or None if it is simple.
+    fn explain_non_simplicity(&self) -> Option<ProblemReport>;
+}
+
+/// Only the self-intersections of each LineString are looked for
+/// (the LineStrings of a MultiLineString may cross each other).
+impl<T: GeoFloat> Simplicity for LineString<T> {
+    fn is_simple(&self) -> bool {
+        !utils::linestring_has_self_intersection(self)
+    }
+
+    fn explain_non_simplicity(&self) -> Option<ProblemReport> {
+        let (segment, _) = utils::self_intersecting_segments(self)?;
+        Some(ProblemReport(vec![ProblemAtPosition(
+            Problem::SelfIntersection,
+            ProblemPosition::LineString(CoordinatePosition(Some(segment.index))),
+        )]))
+    }
+}
+
+impl<T: GeoFloat> Simplicity for MultiLineString<T> {
+    fn is_simple(&self) -> bool {
+        self.iter().all(|ls| ls.is_simple())
+    }
+
+    fn explain_non_simplicity(&self) -> Option<ProblemReport> {
+        let reason: Vec<ProblemAtPosition> = self
+            .iter()
+            .enumerate()
+            .filter_map(|(j, ls)| {
+                let (segment, _) = utils::self_intersecting_segments(ls)?;
+                Some(ProblemAtPosition(
+                    Problem::SelfIntersection,
+                    ProblemPosition::MultiLineString(
+                        GeometryPosition(j),
+                        CoordinatePosition(Some(segment.index)),
+                    ),
+                ))
+            })
+            .collect();
+        if reason.is_empty() {
+            None
+        } else {
+            Some(ProblemReport(reason))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
+        ProblemReport, Simplicity, Valid,
+    };
+    use geo_types::{LineString, MultiLineString};
+    use geos::Geom;
+
+    #[test]
+    fn test_linestring_figure_eight_is_not_simple() {
+        let ls = LineString::from(vec![(0., 0.), (2., 2.), (2., 0.), (0., 2.), (0., 0.)]);
+        // This is valid, but not simple
+        assert!(ls.is_valid());
+        assert!(!ls.is_simple());
+        assert_eq!(
+            ls.explain_non_simplicity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::LineString(CoordinatePosition(Some(0)))
+            )]))
+        );
+
+        // Test that the linestring has the same simplicity than its GEOS equivalent
+        let linestring_geos: geos::Geometry = (&ls).try_into().unwrap();
+        assert_eq!(ls.is_simple(), linestring_geos.is_simple().unwrap());
+
+        // A closed LineString touching itself at its endpoints is simple
+        let ls = LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.), (0., 0.)]);
+        assert!(ls.is_simple());
+        assert!(ls.explain_non_simplicity().is_none());
+        let linestring_geos: geos::Geometry = (&ls).try_into().unwrap();
+        assert_eq!(ls.is_simple(), linestring_geos.is_simple().unwrap());
+    }
+
+    #[test]
+    fn test_multilinestring_is_simple() {
+        let mls = MultiLineString(vec![
+            LineString::from(vec![(0., 0.), (1., 1.)]),
+            LineString::from(vec![(5., 0.), (7., 2.), (7., 0.), (5., 2.)]),
+        ]);
+        assert!(mls.is_valid());
+        assert!(!mls.is_simple());
+        assert_eq!(
+            mls.explain_non_simplicity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::SelfIntersection,
+                ProblemPosition::MultiLineString(GeometryPosition(1), CoordinatePosition(Some(0)))
+            )]))
+        );
+
+        let mls = MultiLineString(vec![mls.0[0].clone()]);
+        assert!(mls.is_simple());
+    }
+
+    #[test]
+    fn test_linestring_large_spiral_is_simple() {
+        // An Archimedean spiral of 100k vertices (about 160 turns), which is out of reach
+        // of a quadratic scan of the pairs of segments
+        let mut ls: LineString = (0..100_000)
+            .map(|k| {
+                let theta = k as f64 * 0.01;
+                (theta * theta.cos(), theta * theta.sin())
+            })
+            .collect::<Vec<_>>()
+            .into();
+        assert!(ls.is_simple());
+
+        // Going back to the center crosses all the turns of the spiral
+        ls.0.push((0.5, 0.5).into());
+        assert!(!ls.is_simple());
+    }
+}
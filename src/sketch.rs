@@ -0,0 +1,128 @@
+use crate::ogc::locate_problem;
+use crate::ProblemReport;
+use geo::CoordsIter;
+use geo_types::{Coord, Geometry, Line, LineString, Polygon};
+
+impl ProblemReport {
+    /// Draw a small ASCII sketch of `geom` (the geometry the problems were reported for),
+    /// `width` characters wide and `height` lines high, on which the location of each
+    /// problem is marked with an `X`.
+    ///
+    /// The segments and the points of the geometry are drawn with `*`
+    /// (the non-finite coordinates being ignored).
+    pub fn sketch(&self, geom: &Geometry, width: usize, height: usize) -> String {
+        let mut grid = vec![vec![' '; width]; height];
+        let finite = |c: &Coord| c.x.is_finite() && c.y.is_finite();
+        let coords: Vec<Coord> = geom.coords_iter().filter(finite).collect();
+        if width == 0 || height == 0 || coords.is_empty() {
+            return render(&grid);
+        }
+
+        let (min, max) = coords.iter().fold((coords[0], coords[0]), |(min, max), c| {
+            (
+                Coord {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coord {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            )
+        });
+        // The cell (column, row) of a coordinate, the first row being at the top
+        let cell = |c: Coord| {
+            let scale = |v: f64, min: f64, max: f64, n: usize| {
+                if max > min {
+                    ((v - min) / (max - min) * (n - 1) as f64).round() as usize
+                } else {
+                    (n - 1) / 2
+                }
+            };
+            (
+                scale(c.x, min.x, max.x, width),
+                height - 1 - scale(c.y, min.y, max.y, height),
+            )
+        };
+
+        for line in segments(geom) {
+            if !finite(&line.start) || !finite(&line.end) {
+                continue;
+            }
+            let (start, end) = (cell(line.start), cell(line.end));
+            let steps = 2 * start.0.abs_diff(end.0).max(start.1.abs_diff(end.1)) + 1;
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+                let (col, row) = cell(Coord {
+                    x: line.start.x + t * (line.end.x - line.start.x),
+                    y: line.start.y + t * (line.end.y - line.start.y),
+                });
+                grid[row][col] = '*';
+            }
+        }
+        for c in coords {
+            let (col, row) = cell(c);
+            grid[row][col] = '*';
+        }
+        for problem in self.0.iter() {
+            if let Some(c) = locate_problem(geom, problem).filter(finite) {
+                let (col, row) = cell(c);
+                grid[row][col] = 'X';
+            }
+        }
+        render(&grid)
+    }
+}
+
+/// Return the segments of a geometry.
+fn segments(geom: &Geometry) -> Vec<Line> {
+    let polygon_segments = |p: &Polygon| -> Vec<Line> {
+        std::iter::once(p.exterior())
+            .chain(p.interiors())
+            .flat_map(LineString::lines)
+            .collect()
+    };
+    match geom {
+        Geometry::Point(_) | Geometry::MultiPoint(_) => vec![],
+        Geometry::Line(l) => vec![*l],
+        Geometry::LineString(ls) => ls.lines().collect(),
+        Geometry::MultiLineString(mls) => mls.iter().flat_map(LineString::lines).collect(),
+        Geometry::Polygon(p) => polygon_segments(p),
+        Geometry::MultiPolygon(mp) => mp.iter().flat_map(polygon_segments).collect(),
+        Geometry::Rect(r) => r.to_lines().to_vec(),
+        Geometry::Triangle(t) => t.to_lines().to_vec(),
+        Geometry::GeometryCollection(gc) => gc.iter().flat_map(segments).collect(),
+    }
+}
+
+fn render(grid: &[Vec<char>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Valid;
+    use geo_types::{Geometry, LineString, Polygon};
+
+    #[test]
+    fn test_sketch_self_intersection() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        let report = p.explain_invalidity().unwrap();
+        let sketch = report.sketch(&Geometry::Polygon(p), 9, 5);
+        let lines: Vec<&str> = sketch.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|line| line.chars().count() == 9));
+        // The self-intersection point (2, 1) is in the middle of the sketch
+        assert_eq!(lines[2].chars().nth(4), Some('X'));
+        assert_eq!(sketch.matches('X').count(), 1);
+        // The corners of the polygon are drawn
+        assert_eq!(lines[0].chars().next(), Some('*'));
+        assert_eq!(lines[4].chars().last(), Some('*'));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{ProblemAtPosition, Valid};
+use geo::MapCoords;
+use geo_types::{Coord, CoordFloat};
+
+/// Check the validity of a geometry once its coordinates are snapped to a grid,
+/// as many databases do when importing geometries.
+///
+/// Geometries that are valid with floating point coordinates may become invalid
+/// once snapped (e.g. a vertex very close to a segment ends up on this segment).
+pub trait SnapValidity<T: CoordFloat> {
+    /// Return the geometry with its coordinates snapped to a grid of the given cell size
+    /// (each coordinate being rounded to the nearest multiple of `grid`), as PostGIS
+    /// `ST_SnapToGrid` does (but the repeated points are kept).
+    ///
+    /// This fixes many problems caused by imprecise coordinates (e.g. an interior ring
+    /// crossing the exterior ring by a tiny amount), but may also introduce new ones.
+    fn snap_to_grid(&self, grid: f64) -> Self
+    where
+        Self: Sized;
+    /// Snap the coordinates of the geometry to a grid of the given cell size
+    /// (each coordinate being rounded to the nearest multiple of `grid`) and check the
+    /// validity of the result:
+    /// - `Ok(true)` is returned if the snapped geometry is valid,
+    /// - `Ok(false)` is returned if the geometry is already invalid before snapping,
+    /// - `Err(problems)` is returned if the geometry is valid but becomes invalid
+    ///   once snapped, with the problems of the snapped geometry.
+    fn is_valid_after_snap(&self, grid: f64) -> Result<bool, Vec<ProblemAtPosition>>;
+}
+
+impl<T, G> SnapValidity<T> for G
+where
+    T: CoordFloat,
+    G: Valid + MapCoords<T, T, Output = G>,
+{
+    fn snap_to_grid(&self, grid: f64) -> Self {
+        let grid = T::from(grid).unwrap();
+        self.map_coords(|c| Coord {
+            x: (c.x / grid).round() * grid,
+            y: (c.y / grid).round() * grid,
+        })
+    }
+
+    fn is_valid_after_snap(&self, grid: f64) -> Result<bool, Vec<ProblemAtPosition>> {
+        if !self.is_valid() {
+            return Ok(false);
+        }
+        match self.snap_to_grid(grid).explain_invalidity() {
+            None => Ok(true),
+            Some(report) => Err(report.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SnapValidity, Valid};
+    use geo_types::{LineString, Polygon};
+
+    #[test]
+    fn test_snap_polygon_becomes_invalid() {
+        // The vertex (5, 0.4) ends up on the first segment of the ring
+        // once snapped to a grid of size 1
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (10., 0.),
+                (10., 2.),
+                (5., 0.4),
+                (0., 2.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert!(p.is_valid());
+        assert!(p.is_valid_after_snap(1.).is_err());
+
+        // A finer grid keeps the polygon valid
+        assert_eq!(p.is_valid_after_snap(0.1), Ok(true));
+
+        // An invalid polygon is reported as such before snapping
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(p.is_valid_after_snap(1.), Ok(false));
+    }
+
+    #[test]
+    fn test_snap_to_grid_polygon_becomes_valid() {
+        // One vertex of the interior ring is outside the exterior ring by 1e-10
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (1., 1.),
+                (4. + 1e-10, 2.),
+                (1., 3.),
+                (1., 1.),
+            ])],
+        );
+        assert!(!p.is_valid());
+
+        // Once snapped, the interior ring touches the exterior ring at a point
+        let snapped = p.snap_to_grid(0.01);
+        assert_eq!(snapped.interiors()[0].0[1], (4., 2.).into());
+        assert!(snapped.is_valid());
+    }
+}
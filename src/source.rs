@@ -0,0 +1,280 @@
+use crate::{
+    CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition, ProblemPosition,
+    ProblemReport, RingRole,
+};
+use geo_types::{Coord, LineString, MultiPolygon, Polygon};
+
+/// A read-only access to the coordinates of a MultiPolygon, which don't need to be
+/// stored in memory as geo-types geometries (they can be read from a memory-mapped file,
+/// see `FlatCoords`).
+///
+/// The first ring of each Polygon is its exterior ring, the other ones are its interior rings.
+/// Rings don't need to be closed (they are closed as by geo-types when a Polygon is loaded).
+pub trait CoordSource {
+    /// Return the number of Polygons.
+    fn num_polygons(&self) -> usize;
+    /// Return the number of rings (exterior ring included) of the `polygon`-th Polygon.
+    fn num_rings(&self, polygon: usize) -> usize;
+    /// Return the number of coordinates of a ring.
+    fn ring_len(&self, polygon: usize, ring: usize) -> usize;
+    /// Return the `index`-th coordinate of a ring.
+    fn coord(&self, polygon: usize, ring: usize, index: usize) -> Coord<f64>;
+
+    /// Load the `polygon`-th Polygon in memory
+    /// (to run the relational checks of the Valid trait on it).
+    fn polygon(&self, polygon: usize) -> Polygon<f64> {
+        let ring = |r: usize| {
+            LineString(
+                (0..self.ring_len(polygon, r))
+                    .map(|i| self.coord(polygon, r, i))
+                    .collect(),
+            )
+        };
+        let n_rings = self.num_rings(polygon);
+        if n_rings == 0 {
+            return Polygon::new(LineString(vec![]), vec![]);
+        }
+        Polygon::new(ring(0), (1..n_rings).map(ring).collect())
+    }
+}
+
+impl CoordSource for MultiPolygon<f64> {
+    fn num_polygons(&self) -> usize {
+        self.0.len()
+    }
+
+    fn num_rings(&self, polygon: usize) -> usize {
+        1 + self.0[polygon].interiors().len()
+    }
+
+    fn ring_len(&self, polygon: usize, ring: usize) -> usize {
+        match ring {
+            0 => self.0[polygon].exterior().0.len(),
+            _ => self.0[polygon].interiors()[ring - 1].0.len(),
+        }
+    }
+
+    fn coord(&self, polygon: usize, ring: usize, index: usize) -> Coord<f64> {
+        match ring {
+            0 => self.0[polygon].exterior().0[index],
+            _ => self.0[polygon].interiors()[ring - 1].0[index],
+        }
+    }
+}
+
+/// The coordinates of a MultiPolygon stored in a flat buffer of bytes (typically a
+/// memory-mapped file), only the offset of each ring being kept in memory.
+///
+/// All the values are stored in little-endian order:
+/// - the number of Polygons (u64),
+/// - for each Polygon, its number of rings (u64) followed by the number of coordinates
+///   of each ring (u64),
+/// - the coordinates of all the rings, in the same order, as pairs of f64 (x, y).
+///
+/// Such a buffer can be written with `FlatCoords::encode`.
+#[derive(Debug, Clone)]
+pub struct FlatCoords<'a> {
+    data: &'a [u8],
+    /// The offset (in the buffer) and the number of coordinates of each ring of each Polygon.
+    rings: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a> FlatCoords<'a> {
+    /// Read the header of the buffer (returning None if it doesn't follow the expected layout).
+    pub fn new(data: &'a [u8]) -> Option<Self> {
+        let mut cursor = 0;
+        let mut read_u64 = || {
+            let bytes = data.get(cursor..cursor + 8)?;
+            cursor += 8;
+            usize::try_from(u64::from_le_bytes(bytes.try_into().unwrap())).ok()
+        };
+        let n_polygons = read_u64()?;
+        let mut lengths = Vec::new();
+        for _ in 0..n_polygons {
+            let n_rings = read_u64()?;
+            lengths.push(
+                (0..n_rings)
+                    .map(|_| read_u64())
+                    .collect::<Option<Vec<_>>>()?,
+            );
+        }
+        let mut offset = 8 * (1 + lengths.iter().map(|l| 1 + l.len()).sum::<usize>());
+        let mut rings = Vec::with_capacity(n_polygons);
+        for polygon_lengths in lengths {
+            let mut polygon_rings = Vec::with_capacity(polygon_lengths.len());
+            for len in polygon_lengths {
+                polygon_rings.push((offset, len));
+                offset = len.checked_mul(16)?.checked_add(offset)?;
+            }
+            rings.push(polygon_rings);
+        }
+        if offset > data.len() {
+            return None;
+        }
+        Some(FlatCoords { data, rings })
+    }
+
+    /// Encode the coordinates of a MultiPolygon in the layout read by `FlatCoords::new`.
+    pub fn encode(mp: &MultiPolygon<f64>) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend((mp.0.len() as u64).to_le_bytes());
+        for polygon in mp.iter() {
+            out.extend((1 + polygon.interiors().len() as u64).to_le_bytes());
+            for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                out.extend((ring.0.len() as u64).to_le_bytes());
+            }
+        }
+        for polygon in mp.iter() {
+            for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                for c in ring.0.iter() {
+                    out.extend(c.x.to_le_bytes());
+                    out.extend(c.y.to_le_bytes());
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<'a> CoordSource for FlatCoords<'a> {
+    fn num_polygons(&self) -> usize {
+        self.rings.len()
+    }
+
+    fn num_rings(&self, polygon: usize) -> usize {
+        self.rings[polygon].len()
+    }
+
+    fn ring_len(&self, polygon: usize, ring: usize) -> usize {
+        self.rings[polygon][ring].1
+    }
+
+    fn coord(&self, polygon: usize, ring: usize, index: usize) -> Coord<f64> {
+        let start = self.rings[polygon][ring].0 + 16 * index;
+        let read_f64 = |at: usize| f64::from_le_bytes(self.data[at..at + 8].try_into().unwrap());
+        Coord {
+            x: read_f64(start),
+            y: read_f64(start + 8),
+        }
+    }
+}
+
+/// Perform the structural checks of a MultiPolygon (`NotFinite` and `TooFewPoints`)
+/// by reading its coordinates one by one from a CoordSource, without loading it in memory.
+///
+/// The problems are reported at the same positions as by the Valid trait on
+/// the corresponding MultiPolygon. The relational checks (such as `SelfIntersection`)
+/// are not performed: they can be run on each Polygon loaded with `CoordSource::polygon`.
+pub fn validate_structure<S: CoordSource + ?Sized>(source: &S) -> Option<ProblemReport> {
+    let mut reason = Vec::new();
+    for j in 0..source.num_polygons() {
+        for r in 0..source.num_rings(j) {
+            let role = match r {
+                0 => RingRole::Exterior,
+                _ => RingRole::Interior(r - 1),
+            };
+            let position = |i: Option<usize>| {
+                ProblemPosition::MultiPolygon(GeometryPosition(j), role, CoordinatePosition(i))
+            };
+            let len = source.ring_len(j, r);
+            let mut previous: Option<Coord<f64>> = None;
+            let mut n_distinct = 0;
+            for i in 0..len {
+                let c = source.coord(j, r, i);
+                if !c.x.is_finite() || !c.y.is_finite() {
+                    reason.push(ProblemAtPosition(Problem::NotFinite, position(Some(i))));
+                }
+                if previous != Some(c) {
+                    n_distinct += 1;
+                }
+                previous = Some(c);
+            }
+            // Account for the closing coordinate added by geo-types
+            let closed = len == 0 || source.coord(j, r, 0) == source.coord(j, r, len - 1);
+            let (closed_len, n_distinct) = match closed {
+                true => (len, n_distinct),
+                false => (len + 1, n_distinct + 1),
+            };
+            if n_distinct < 4 {
+                reason.push(ProblemAtPosition(
+                    Problem::TooFewPoints,
+                    position(closed_len.checked_sub(2)),
+                ));
+            }
+        }
+    }
+    if reason.is_empty() {
+        None
+    } else {
+        Some(ProblemReport(reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        validate_structure, CoordSource, CoordinatePosition, FlatCoords, GeometryPosition, Problem,
+        ProblemAtPosition, ProblemPosition, ProblemReport, RingRole,
+    };
+    use geo_types::{LineString, MultiPolygon, Polygon};
+    use std::io::Write;
+
+    #[test]
+    fn test_validate_structure_mmap() {
+        let mp = MultiPolygon(vec![
+            Polygon::new(
+                LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+                vec![LineString::from(vec![(1., 1.), (2., 1.), (1., 1.)])],
+            ),
+            Polygon::new(
+                LineString::from(vec![
+                    (10., 0.),
+                    (14., 0.),
+                    (f64::NAN, 4.),
+                    (10., 4.),
+                    (10., 0.),
+                ]),
+                vec![],
+            ),
+        ]);
+
+        let path = std::env::temp_dir().join(format!("gvc-flat-{}.bin", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(&FlatCoords::encode(&mp))
+            .unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let mmap = unsafe { memmap2::Mmap::map(&file).unwrap() };
+        let source = FlatCoords::new(&mmap).unwrap();
+
+        assert_eq!(source.num_polygons(), 2);
+        assert_eq!(source.polygon(0), mp.0[0]);
+        assert_eq!(
+            validate_structure(&source),
+            Some(ProblemReport(vec![
+                ProblemAtPosition(
+                    Problem::TooFewPoints,
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(0),
+                        RingRole::Interior(0),
+                        CoordinatePosition(Some(1))
+                    )
+                ),
+                ProblemAtPosition(
+                    Problem::NotFinite,
+                    ProblemPosition::MultiPolygon(
+                        GeometryPosition(1),
+                        RingRole::Exterior,
+                        CoordinatePosition(Some(2))
+                    )
+                ),
+            ]))
+        );
+        assert_eq!(validate_structure(&source), validate_structure(&mp));
+        std::fs::remove_file(&path).unwrap();
+
+        // A truncated buffer is rejected
+        let data = FlatCoords::encode(&mp);
+        assert!(FlatCoords::new(&data[..data.len() - 1]).is_none());
+    }
+}
@@ -1,8 +1,10 @@
+use crate::collect::{self, CollectProblems, PositionContext};
 use crate::{
-    utils, CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+    utils, CoordinatePosition, LineDegeneracy, Problem, ProblemAtPosition, ProblemPosition,
+    ProblemReport, Valid, ValidationConfig,
 };
 use geo::CoordNum;
-use geo_types::Triangle;
+use geo_types::{Coord, Triangle};
 use num_traits::Float;
 
 /// As stated in geo-types/src/geometry/triangles.rs,
@@ -11,83 +13,161 @@ impl<T> Valid for Triangle<T>
 where
     T: CoordNum + Float,
 {
-    fn is_valid(&self) -> bool {
-        if utils::check_coord_is_not_finite(&self.0)
-            || utils::check_coord_is_not_finite(&self.1)
-            || utils::check_coord_is_not_finite(&self.2)
+    fn is_valid_with(&self, config: &ValidationConfig) -> bool {
+        if utils::check_coord_is_not_finite(&self.v1())
+            || utils::check_coord_is_not_finite(&self.v2())
+            || utils::check_coord_is_not_finite(&self.v3())
         {
             return false;
         }
+        if [self.v1(), self.v2(), self.v3()].iter().any(|coord| {
+            utils::check_coord_has_negative_zero(coord, config)
+                || utils::check_coord_out_of_geographic_bounds(coord, config)
+        }) {
+            return false;
+        }
 
-        if self.0 == self.1 || self.1 == self.2 || self.2 == self.0 {
+        let tolerance = config.near_duplicate_tolerance;
+        if self
+            .to_lines()
+            .iter()
+            .any(|line| line.is_degenerate(tolerance))
+        {
             return false;
         }
 
-        if utils::robust_check_points_are_collinear::<T>(&self.0, &self.1, &self.2) {
+        if utils::robust_check_points_are_collinear::<T>(
+            &self.v1(),
+            &self.v2(),
+            &self.v3(),
+            config.collinearity_eps,
+        ) {
             return false;
         }
         true
     }
-    fn explain_invalidity(&self) -> Option<ProblemReport> {
-        let mut reason = Vec::new();
+    fn explain_invalidity_with(&self, config: &ValidationConfig) -> Option<ProblemReport> {
+        collect::explain(self, config)
+    }
+}
 
-        if utils::check_coord_is_not_finite(&self.0) {
-            reason.push(ProblemAtPosition(
+impl<T> CollectProblems for Triangle<T>
+where
+    T: CoordNum + Float,
+{
+    fn collect_into(
+        &self,
+        out: &mut Vec<ProblemAtPosition>,
+        config: &ValidationConfig,
+        ctx: PositionContext,
+    ) {
+        if utils::check_coord_is_not_finite(&self.v1()) {
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Triangle(CoordinatePosition(0)),
+                ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(0)))),
             ));
         }
-        if utils::check_coord_is_not_finite(&self.1) {
-            reason.push(ProblemAtPosition(
+        if utils::check_coord_is_not_finite(&self.v2()) {
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Triangle(CoordinatePosition(1)),
+                ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(1)))),
             ));
         }
-        if utils::check_coord_is_not_finite(&self.2) {
-            reason.push(ProblemAtPosition(
+        if utils::check_coord_is_not_finite(&self.v3()) {
+            out.push(ProblemAtPosition(
                 Problem::NotFinite,
-                ProblemPosition::Triangle(CoordinatePosition(2)),
+                ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(2)))),
             ));
         }
+        for (i, coord) in [self.v1(), self.v2(), self.v3()].iter().enumerate() {
+            if utils::check_coord_has_negative_zero(coord, config) {
+                out.push(ProblemAtPosition(
+                    Problem::SignedZero,
+                    ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(i)))),
+                ));
+            }
+            if utils::check_coord_out_of_geographic_bounds(coord, config) {
+                out.push(ProblemAtPosition(
+                    Problem::OutOfGeographicBounds,
+                    ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(i)))),
+                ));
+            }
+        }
 
         // We wont check if the points are collinear if they are identical
         let mut identical = false;
 
-        if self.0 == self.1 || self.0 == self.2 {
-            reason.push(ProblemAtPosition(
+        // The sides of the triangle are v1-v2, v2-v3 and v3-v1
+        let tolerance = config.near_duplicate_tolerance;
+        let [side1, side2, side3] = self.to_lines();
+        if side1.is_degenerate(tolerance) || side3.is_degenerate(tolerance) {
+            out.push(ProblemAtPosition(
                 Problem::IdenticalCoords,
-                ProblemPosition::Triangle(CoordinatePosition(0)),
+                ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(0)))),
             ));
             identical = true;
         }
 
-        if self.1 == self.2 {
-            reason.push(ProblemAtPosition(
+        if side2.is_degenerate(tolerance) {
+            out.push(ProblemAtPosition(
                 Problem::IdenticalCoords,
-                ProblemPosition::Triangle(CoordinatePosition(1)),
+                ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(Some(1)))),
             ));
             identical = true;
         }
 
-        if !identical && utils::robust_check_points_are_collinear::<T>(&self.0, &self.1, &self.2) {
-            reason.push(ProblemAtPosition(
+        if !identical
+            && utils::robust_check_points_are_collinear::<T>(
+                &self.v1(),
+                &self.v2(),
+                &self.v3(),
+                config.collinearity_eps,
+            )
+        {
+            out.push(ProblemAtPosition(
                 Problem::CollinearCoords,
-                ProblemPosition::Triangle(CoordinatePosition(-1)),
+                ctx.wrap(ProblemPosition::Triangle(CoordinatePosition(
+                    middle_vertex(self),
+                ))),
             ));
         }
+    }
+}
 
-        if reason.is_empty() {
-            None
-        } else {
-            Some(ProblemReport(reason))
-        }
+/// Return the index of the vertex lying between the two others on their line
+/// (i.e. the vertex opposite the longest side) when the vertices are collinear,
+/// or None if the three vertices are coincident.
+fn middle_vertex<T: CoordNum + Float>(triangle: &Triangle<T>) -> Option<usize> {
+    let squared_length = |a: Coord<T>, b: Coord<T>| (a.x - b.x).powi(2) + (a.y - b.y).powi(2);
+    let (v1, v2, v3) = (triangle.v1(), triangle.v2(), triangle.v3());
+    let opposite_sides = [
+        squared_length(v2, v3),
+        squared_length(v3, v1),
+        squared_length(v1, v2),
+    ];
+    let (i, longest) =
+        opposite_sides
+            .iter()
+            .enumerate()
+            .fold((None, T::zero()), |(i, longest), (j, &length)| {
+                if length > longest {
+                    (Some(j), length)
+                } else {
+                    (i, longest)
+                }
+            });
+    if longest > T::zero() {
+        i
+    } else {
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport, Valid,
+        CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, ProblemReport,
+        StrictnessProfile, Valid, ValidationConfig,
     };
     use geo_types::Triangle;
 
@@ -106,7 +186,7 @@ mod tests {
             t.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::IdenticalCoords,
-                ProblemPosition::Triangle(CoordinatePosition(1)),
+                ProblemPosition::Triangle(CoordinatePosition(Some(1))),
             )]))
         );
     }
@@ -119,7 +199,89 @@ mod tests {
             t.explain_invalidity(),
             Some(ProblemReport(vec![ProblemAtPosition(
                 Problem::CollinearCoords,
-                ProblemPosition::Triangle(CoordinatePosition(-1)),
+                ProblemPosition::Triangle(CoordinatePosition(Some(1))),
+            )]))
+        );
+
+        // The position designates the vertex lying between the two others
+        let t = Triangle((1., 1.).into(), (0., 0.).into(), (2., 2.).into());
+        assert_eq!(
+            t.explain_invalidity(),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::CollinearCoords,
+                ProblemPosition::Triangle(CoordinatePosition(Some(0))),
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_triangle_not_collinear_large_coordinates() {
+        // The orientation determinant is 1e8 * 1e8 - (1e8 + 1) * (1e8 - 1) = 1,
+        // but it rounds to 0 when computed with f64 (the products are around 1e16)
+        let t = Triangle(
+            (0., 0.).into(),
+            (1e8, 1e8 + 1.).into(),
+            (1e8 - 1., 1e8).into(),
+        );
+        assert!(t.is_valid());
+        assert!(t.explain_invalidity().is_none());
+    }
+
+    #[test]
+    fn test_triangle_nearly_collinear_strictness_profiles() {
+        // The third point is 1e-10 away from the line passing through the two others
+        let t = Triangle((0., 0.).into(), (1., 0.).into(), (2., 1e-10).into());
+        assert!(t.is_valid());
+
+        let lenient = StrictnessProfile::Lenient.config();
+        assert!(t.is_valid_with(&lenient));
+        assert!(t.explain_invalidity_with(&lenient).is_none());
+
+        let strict = StrictnessProfile::Strict.config();
+        assert!(!t.is_valid_with(&strict));
+        assert_eq!(
+            t.explain_invalidity_with(&strict),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::CollinearCoords,
+                ProblemPosition::Triangle(CoordinatePosition(Some(1))),
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_triangle_collinearity_eps_large_coordinates() {
+        // Web Mercator coordinates: the third point is 1mm away from the line passing
+        // through the two others, so the orientation determinant is about 10
+        let t = Triangle(
+            (2_000_000., 6_000_000.).into(),
+            (2_010_000., 6_000_000.).into(),
+            (2_005_000., 6_000_000.001).into(),
+        );
+        assert!(t.is_valid());
+        assert!(t.is_valid_with(&StrictnessProfile::Strict.config()));
+
+        let config = ValidationConfig {
+            collinearity_eps: 100.,
+            ..Default::default()
+        };
+        assert_eq!(
+            t.explain_invalidity_with(&config),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::CollinearCoords,
+                ProblemPosition::Triangle(CoordinatePosition(Some(2))),
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_triangle_near_duplicate_points_strict_profile() {
+        let t = Triangle((0., 0.).into(), (1., 1.).into(), (1. + 1e-12, 1.).into());
+        assert!(t.is_valid());
+        assert_eq!(
+            t.explain_invalidity_with(&StrictnessProfile::Strict.config()),
+            Some(ProblemReport(vec![ProblemAtPosition(
+                Problem::IdenticalCoords,
+                ProblemPosition::Triangle(CoordinatePosition(Some(1))),
             )]))
         );
     }
@@ -132,7 +294,7 @@ mod tests {
     //         t.explain_invalidity(),
     //         Some(vec![ProblemAtPosition(
     //             Problem::CollinearCoords,
-    //             ProblemPosition::Triangle(CoordinatePosition(-1)),
+    //             ProblemPosition::Triangle(CoordinatePosition(None)),
     //         )])
     //     );
     // }
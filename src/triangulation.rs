@@ -0,0 +1,142 @@
+use crate::{
+    CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, RingRole, Valid,
+    ValidationConfig,
+};
+use geo::winding_order::{Winding, WindingOrder};
+use geo::GeoFloat;
+use geo_types::Polygon;
+use num_traits::FromPrimitive;
+
+/// The way a Polygon is going to be triangulated (see `TriangulationValidity`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriMode {
+    /// A triangle fan around the first vertex of the exterior ring:
+    /// the Polygon must be convex and without interior rings.
+    Fan,
+    /// Ear clipping (as done by earcut): the Polygon may have interior rings.
+    EarClipping,
+}
+
+/// Check that a Polygon can be safely triangulated (e.g. to be rendered as a mesh).
+pub trait TriangulationValidity {
+    /// Return the problems preventing the Polygon from being triangulated in the given mode,
+    /// or None if it can be triangulated:
+    /// - the problems of the Polygon reported by the Valid trait,
+    /// - `IncorrectRingOrientation` if the exterior ring is not wound counter-clockwise,
+    /// - in `TriMode::Fan`, `InteriorRingNotAllowed` for each interior ring
+    ///   and `NotConvex` if the Polygon is not convex.
+    fn validate_for_triangulation(&self, mode: TriMode) -> Option<Vec<ProblemAtPosition>>;
+}
+
+impl<T> TriangulationValidity for Polygon<T>
+where
+    T: GeoFloat + FromPrimitive,
+{
+    fn validate_for_triangulation(&self, mode: TriMode) -> Option<Vec<ProblemAtPosition>> {
+        let config = ValidationConfig {
+            // The interior rings are reported distinctly
+            check_convexity: mode == TriMode::Fan && self.interiors().is_empty(),
+            ..Default::default()
+        };
+        let mut problems = self
+            .explain_invalidity_with(&config)
+            .map(|report| report.0)
+            .unwrap_or_default();
+
+        if self.exterior().winding_order() == Some(WindingOrder::Clockwise) {
+            problems.push(ProblemAtPosition(
+                Problem::IncorrectRingOrientation,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None)),
+            ));
+        }
+        if mode == TriMode::Fan {
+            for j in 0..self.interiors().len() {
+                problems.push(ProblemAtPosition(
+                    Problem::InteriorRingNotAllowed,
+                    ProblemPosition::Polygon(RingRole::Interior(j), CoordinatePosition(None)),
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            None
+        } else {
+            Some(problems)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, RingRole, TriMode,
+        TriangulationValidity,
+    };
+    use geo_types::{LineString, Polygon};
+
+    #[test]
+    fn test_triangulation_fan_safe_triangle() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (2., 0.), (1., 2.), (0., 0.)]),
+            vec![],
+        );
+        assert!(p.validate_for_triangulation(TriMode::Fan).is_none());
+        assert!(p.validate_for_triangulation(TriMode::EarClipping).is_none());
+
+        // The same triangle, wound clockwise
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (1., 2.), (2., 0.), (0., 0.)]),
+            vec![],
+        );
+        assert_eq!(
+            p.validate_for_triangulation(TriMode::Fan),
+            Some(vec![ProblemAtPosition(
+                Problem::IncorrectRingOrientation,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(None))
+            )])
+        );
+    }
+
+    #[test]
+    fn test_triangulation_fan_rejects_holes() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]),
+            vec![LineString::from(vec![
+                (4., 4.),
+                (4., 6.),
+                (6., 6.),
+                (6., 4.),
+                (4., 4.),
+            ])],
+        );
+        assert_eq!(
+            p.validate_for_triangulation(TriMode::Fan),
+            Some(vec![ProblemAtPosition(
+                Problem::InteriorRingNotAllowed,
+                ProblemPosition::Polygon(RingRole::Interior(0), CoordinatePosition(None))
+            )])
+        );
+        assert!(p.validate_for_triangulation(TriMode::EarClipping).is_none());
+
+        // A reflex vertex prevents a fan triangulation
+        let p = Polygon::new(
+            LineString::from(vec![
+                (0., 0.),
+                (4., 0.),
+                (4., 4.),
+                (2., 1.),
+                (0., 4.),
+                (0., 0.),
+            ]),
+            vec![],
+        );
+        assert_eq!(
+            p.validate_for_triangulation(TriMode::Fan),
+            Some(vec![ProblemAtPosition(
+                Problem::NotConvex,
+                ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(3)))
+            )])
+        );
+        assert!(p.validate_for_triangulation(TriMode::EarClipping).is_none());
+    }
+}
@@ -1,8 +1,60 @@
+use crate::profile::{self, Check};
+use crate::{RingClosure, ValidationConfig};
+use geo::line_intersection::{line_intersection, LineIntersection};
 use geo::RemoveRepeatedPoints;
-use geo::{GeoNum, Intersects};
-use geo_types::{Coord, CoordFloat, LineString};
+use geo::{BoundingRect, EuclideanDistance, GeoFloat, GeoNum, Intersects};
+use geo_types::{Coord, CoordFloat, CoordNum, Line, LineString};
 use num_traits::FromPrimitive;
 use robust::{orient2d, Coord as RobustCoord};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+use std::collections::HashSet;
+
+/// Check if the absolute value of a coordinate is greater than `max_magnitude`.
+///
+/// For the types narrower than f64 (such as f32), the coordinates are also reported
+/// when they are too large for the orientation determinants (sums of products of
+/// differences of coordinates) to be computed without overflowing to infinity,
+/// i.e. above `sqrt(T::max_value() / 8)` (about 6.5e18 for f32).
+pub(crate) fn check_coord_magnitude_is_unsafe<T: CoordFloat>(
+    geom: &Coord<T>,
+    max_magnitude: f64,
+) -> bool {
+    let max_magnitude = match T::max_value().to_f64() {
+        Some(type_max) if type_max < f64::MAX => max_magnitude.min((type_max / 8.).sqrt()),
+        _ => max_magnitude,
+    };
+    [geom.x, geom.y]
+        .iter()
+        .any(|v| v.to_f64().is_some_and(|v| v.abs() > max_magnitude))
+}
+
+/// Check if a coordinate is outside of the range of the geographic coordinates,
+/// i.e. if its longitude (x) isn't in [-180, 180] or its latitude (y) isn't in [-90, 90]
+/// (only reported when `geographic` is set in the ValidationConfig).
+pub(crate) fn check_coord_out_of_geographic_bounds<T: CoordFloat>(
+    geom: &Coord<T>,
+    config: &ValidationConfig,
+) -> bool {
+    let (lon, lat) = match (geom.x.to_f64(), geom.y.to_f64()) {
+        (Some(lon), Some(lat)) => (lon, lat),
+        _ => return false,
+    };
+    // The non-finite coordinates are reported as such
+    config.geographic && lon.is_finite() && lat.is_finite() && (lon.abs() > 180. || lat.abs() > 90.)
+}
+
+/// Check if a coordinate contains a negative zero (only reported when
+/// `check_signed_zeros` is set in the ValidationConfig).
+pub(crate) fn check_coord_has_negative_zero<T: CoordFloat>(
+    geom: &Coord<T>,
+    config: &ValidationConfig,
+) -> bool {
+    config.check_signed_zeros
+        && [geom.x, geom.y]
+            .iter()
+            .any(|v| v.is_zero() && v.is_sign_negative())
+}
 
 pub(crate) fn check_coord_is_not_finite<T: CoordFloat>(geom: &Coord<T>) -> bool {
     if geom.x.is_finite() && geom.y.is_finite() {
@@ -11,24 +63,15 @@ pub(crate) fn check_coord_is_not_finite<T: CoordFloat>(geom: &Coord<T>) -> bool
     true
 }
 
-// pub(crate) fn check_points_are_collinear<T: CoordNum + Float>(p0: &Coord<T>, p1: &Coord<T>, p2: &Coord<T>) -> bool {
-//     let a = p1.x - p0.x;
-//     let b = p1.y - p0.y;
-//     let c = p2.x - p0.x;
-//     let d = p2.y - p0.y;
-//     let det = a * d - b * c;
-//     if det.abs() < T::from(1e-10).unwrap() {
-//         return true;
-//     }
-//     false
-// }
-
+/// Check if three points are collinear, using the exact (robust) orientation test
+/// if `eps` is 0, or comparing the absolute value of the orientation determinant to `eps`.
 pub(crate) fn robust_check_points_are_collinear<T: CoordFloat>(
     p0: &Coord<T>,
     p1: &Coord<T>,
     p2: &Coord<T>,
+    eps: f64,
 ) -> bool {
-    orient2d(
+    let orientation = orient2d(
         RobustCoord {
             x: p0.x.to_f64().unwrap(),
             y: p0.y.to_f64().unwrap(),
@@ -41,7 +84,29 @@ pub(crate) fn robust_check_points_are_collinear<T: CoordFloat>(
             x: p2.x.to_f64().unwrap(),
             y: p2.y.to_f64().unwrap(),
         },
-    ) == 0.
+    );
+    if eps == 0. {
+        orientation == 0.
+    } else {
+        orientation.abs() <= eps
+    }
+}
+
+/// Check if two coordinates are identical, or closer than `tolerance` if it isn't 0.
+pub(crate) fn check_coords_are_identical<T: CoordFloat>(
+    c0: &Coord<T>,
+    c1: &Coord<T>,
+    tolerance: f64,
+) -> bool {
+    if tolerance == 0. {
+        c0 == c1
+    } else {
+        (c0.x - c1.x)
+            .hypot(c0.y - c1.y)
+            .to_f64()
+            .map(|distance| distance <= tolerance)
+            .unwrap_or(false)
+    }
 }
 
 pub(crate) fn check_too_few_points<T: CoordFloat + FromPrimitive>(
@@ -55,20 +120,197 @@ pub(crate) fn check_too_few_points<T: CoordFloat + FromPrimitive>(
     false
 }
 
-pub(crate) fn linestring_has_self_intersection<T: GeoNum>(geom: &LineString<T>) -> bool {
-    // This need more test to see if we detect "spikes" correctly.
-    // Maybe we could also use https://docs.rs/geo/latest/geo/algorithm/line_intersection/fn.line_intersection.html
-    // to compute the intersection, see if it is a single point or not, etc.
-    for (i, line) in geom.lines().enumerate() {
-        for (j, other_line) in geom.lines().enumerate() {
-            if i != j
-                && line.intersects(&other_line)
-                && line.start != other_line.end
-                && line.end != other_line.start
-            {
-                return true;
-            }
-        }
+/// Check if a ring encloses no area: if its vertices are all collinear (checked exactly)
+/// when `tolerance` is 0, or if the absolute value of its signed area is not greater
+/// than `tolerance` otherwise.
+pub(crate) fn ring_has_zero_area<T: CoordFloat>(ring: &LineString<T>, tolerance: f64) -> bool {
+    if tolerance == 0. {
+        let first = match ring.0.first() {
+            Some(first) => first,
+            None => return true,
+        };
+        return match ring.0.iter().find(|c| *c != first) {
+            Some(other) => ring
+                .0
+                .iter()
+                .all(|c| robust_check_points_are_collinear(first, other, c, 0.)),
+            None => true,
+        };
     }
-    false
+    let twice_area = ring.lines().fold(T::zero(), |sum, line| {
+        sum + line.start.x * line.end.y - line.end.x * line.start.y
+    });
+    (twice_area / (T::one() + T::one())).abs().to_f64().unwrap() <= tolerance
+}
+
+/// Return the index of the first coordinate of the segments whose longitude (x) difference
+/// is greater than 180°, i.e. which most probably should cross the antimeridian instead
+/// of going around the whole globe (the segments with non-finite coordinates are ignored).
+pub(crate) fn antimeridian_crossings<T: CoordFloat>(
+    geom: &LineString<T>,
+) -> impl Iterator<Item = usize> + '_ {
+    let half_turn = T::from(180.).unwrap();
+    let finite = |c: &Coord<T>| c.x.is_finite() && c.y.is_finite();
+    geom.0
+        .windows(2)
+        .enumerate()
+        .filter(move |(_, pair)| finite(&pair[0]) && finite(&pair[1]))
+        .filter(move |(_, pair)| (pair[1].x - pair[0].x).abs() > half_turn)
+        .map(|(i, _)| i)
+}
+
+/// Return the index of the coordinates identical to the previous coordinate
+/// (the second occurrence of each repeated point).
+pub(crate) fn repeated_points<T: CoordNum>(
+    geom: &LineString<T>,
+) -> impl Iterator<Item = usize> + '_ {
+    geom.0
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] == pair[1])
+        .map(|(i, _)| i + 1)
+}
+
+pub(crate) fn linestring_has_self_intersection<T: GeoFloat>(geom: &LineString<T>) -> bool {
+    linestring_self_intersection(geom).is_some()
+}
+
+/// Return the first self-intersection point found in a LineString (the start of the shared
+/// part when two segments overlap), if any.
+pub(crate) fn linestring_self_intersection<T: GeoFloat>(geom: &LineString<T>) -> Option<Coord<T>> {
+    let (segment, other_segment) = self_intersecting_segments(geom)?;
+    match line_intersection(segment.line, other_segment.line)? {
+        LineIntersection::SinglePoint { intersection, .. } => Some(intersection),
+        LineIntersection::Collinear { intersection } => Some(intersection.start),
+    }
+}
+
+/// Return the first pair of (non-adjacent) segments of a LineString intersecting each other,
+/// if any (the pair of segments whose indices come first, as if all the pairs were
+/// compared in order), the segment with the lowest index being returned first.
+///
+/// Only the pairs of segments whose bounding boxes intersect (found with an R-tree)
+/// are compared, which avoids comparing all the pairs of segments of large LineStrings.
+/// The segments with non-finite coordinates are ignored.
+pub(crate) fn self_intersecting_segments<T: GeoFloat>(
+    geom: &LineString<T>,
+) -> Option<(IndexedSegment<T>, IndexedSegment<T>)> {
+    profile::timed(Check::SelfIntersection, || {
+        let finite = |c: &Coord<T>| c.x.is_finite() && c.y.is_finite();
+        let segments: Vec<IndexedSegment<T>> = geom
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| finite(&line.start) && finite(&line.end))
+            .map(|(index, line)| IndexedSegment { index, line })
+            .collect();
+        let tree = RTree::bulk_load(
+            segments
+                .iter()
+                .enumerate()
+                .map(|(k, segment)| {
+                    let (start, end) = (segment.line.start, segment.line.end);
+                    GeomWithData::new(Rectangle::from_corners(start.x_y(), end.x_y()), k)
+                })
+                .collect(),
+        );
+        segments.iter().enumerate().find_map(|(k, segment)| {
+            let (start, end) = (segment.line.start, segment.line.end);
+            tree.locate_in_envelope_intersecting(&AABB::from_corners(start.x_y(), end.x_y()))
+                .map(|candidate| candidate.data)
+                .filter(|&other| other > k)
+                .filter(|&other| {
+                    let line = segment.line;
+                    let other_line = segments[other].line;
+                    line.intersects(&other_line)
+                        && line.start != other_line.end
+                        && line.end != other_line.start
+                })
+                .min()
+                .map(|other| (segment.clone(), segments[other].clone()))
+        })
+    })
+}
+
+/// A segment of a LineString, along with its index
+/// (i.e. the index of its first coordinate in the LineString).
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedSegment<T: GeoFloat> {
+    pub(crate) index: usize,
+    pub(crate) line: Line<T>,
+}
+
+/// Return the length of the closing segment of a ring if it is short enough (relatively to
+/// the extent of the ring) to have been added by geo-types to close a nearly-closed ring.
+pub(crate) fn nearly_closed_ring_gap<T: GeoFloat>(ring: &LineString<T>) -> Option<T> {
+    let n = ring.0.len();
+    if n < 4 {
+        return None;
+    }
+    let gap = ring.0[n - 2].euclidean_distance(&ring.0[0]);
+    let rect = ring.bounding_rect()?;
+    let diagonal = rect.min().euclidean_distance(&rect.max());
+    if gap > T::zero() && gap <= diagonal * T::from(1e-6).unwrap() {
+        Some(gap)
+    } else {
+        None
+    }
+}
+
+/// Check if the closing coordinate of a ring was provided at its start instead of its end,
+/// i.e. if its first coordinate is repeated (e.g. `[A, A, B, C]`, closed by geo-types
+/// as `[A, A, B, C, A]`).
+pub(crate) fn ring_closed_at_start<T: GeoNum>(ring: &LineString<T>) -> bool {
+    ring.0.len() > 2 && ring.0[0] == ring.0[1]
+}
+
+pub(crate) fn check_ring_not_closed<T: GeoFloat>(
+    ring: &LineString<T>,
+    ring_closure: &RingClosure,
+) -> bool {
+    match (ring_closure, nearly_closed_ring_gap(ring)) {
+        (RingClosure::AutoClose, _) => false,
+        (_, None) => ring_closed_at_start(ring),
+        (RingClosure::Exact, Some(_)) => true,
+        (RingClosure::Snap(tolerance), Some(gap)) => gap > T::from(*tolerance).unwrap(),
+    }
+}
+
+/// A key identifying a coordinate by the bits of its values, to look it up in a hash map
+/// (adding zero turns -0 into 0, as they are equal).
+pub(crate) type CoordKey = [(u64, i16, i8); 2];
+
+pub(crate) fn coord_key<T: GeoFloat>(c: &Coord<T>) -> CoordKey {
+    [
+        (c.x + T::zero()).integer_decode(),
+        (c.y + T::zero()).integer_decode(),
+    ]
+}
+
+/// Check if two rings share a chain of consecutive vertices (i.e. at least one edge,
+/// whatever the direction in which it is traversed).
+///
+/// The edges of `other` are put in a hash set, so that each edge of `ring` is only
+/// looked up once. The edges with non-finite coordinates are ignored.
+pub(crate) fn rings_share_edge<T: GeoFloat>(ring: &LineString<T>, other: &LineString<T>) -> bool {
+    let finite = |c: &Coord<T>| c.x.is_finite() && c.y.is_finite();
+    // The edges are keyed by their endpoints, in the same order whatever their direction
+    let edge_key = |line: &Line<T>| {
+        let (start, end) = (coord_key(&line.start), coord_key(&line.end));
+        if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        }
+    };
+    let other_edges: HashSet<(CoordKey, CoordKey)> = other
+        .lines()
+        .filter(|line| finite(&line.start) && finite(&line.end))
+        .map(|line| edge_key(&line))
+        .collect();
+    ring.lines().any(|line| {
+        line.start != line.end
+            && finite(&line.start)
+            && finite(&line.end)
+            && other_edges.contains(&edge_key(&line))
+    })
 }
@@ -0,0 +1,64 @@
+use crate::{InvalidGeometry, Valid};
+use std::ops::Deref;
+
+/// A geometry that has been checked to be valid (with `Valid::explain_invalidity`),
+/// so that the functions taking it don't have to check it again.
+///
+/// The geometry can be read through `Deref`, but not modified
+/// (use `into_inner` to get it back).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<T>(T);
+
+impl<T: Valid> Validated<T> {
+    /// Check the validity of the geometry, returning the problems encountered if it is invalid.
+    pub fn new(geom: T) -> Result<Self, InvalidGeometry> {
+        geom.validate()?;
+        Ok(Validated(geom))
+    }
+}
+
+impl<T> Validated<T> {
+    /// Return the geometry.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Validated<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Problem, Validated};
+    use geo::Area;
+    use geo_types::{LineString, Polygon};
+
+    #[test]
+    fn test_validated_polygon() {
+        let p = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)]),
+            vec![],
+        );
+        let validated = Validated::new(p.clone()).unwrap();
+        assert_eq!(validated.unsigned_area(), 16.);
+        assert_eq!(validated.into_inner(), p);
+
+        let bowtie = Polygon::new(
+            LineString::from(vec![(0., 0.), (4., 0.), (0., 2.), (4., 2.), (0., 0.)]),
+            vec![],
+        );
+        let err = Validated::new(bowtie).unwrap_err();
+        assert_eq!(err.0 .0[0].0, Problem::SelfIntersection);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{
+    CoordinatePosition, Problem, ProblemAtPosition, ProblemPosition, RingRole, Valid,
+    ValidationConfig,
+};
+use geo_types::Geometry;
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A problem encountered when validating a WKT string, along with the byte offsets
+/// of the part of the source text it refers to (when they can be determined).
+///
+/// The span points at the offending coordinate when the problem concerns a coordinate
+/// (or at the offending number for non-finite coordinates), and at the whole ring
+/// or geometry otherwise, so that `&source[span]` can be underlined in an editor.
+#[derive(Debug, PartialEq)]
+pub struct ProblemInSource(pub ProblemAtPosition, pub Option<Range<usize>>);
+
+impl Display for ProblemInSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.1 {
+            Some(span) => write!(f, "{} (bytes {}..{})", self.0, span.start, span.end),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// The error returned when validating a WKT string.
+#[derive(Debug, PartialEq)]
+pub enum WktValidationError {
+    /// The WKT string can't be parsed.
+    Parse(String),
+    /// The geometry is invalid, see the problems encountered.
+    Invalid(Vec<ProblemInSource>),
+}
+
+impl Display for WktValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WktValidationError::Parse(e) => write!(f, "Unable to parse WKT: {}", e),
+            WktValidationError::Invalid(problems) => {
+                let problems = problems
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                write!(f, "{}", problems)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WktValidationError {}
+
+/// Parse a WKT string and check the validity of the resulting geometry.
+///
+/// On success, the parsed geometry is returned. Otherwise, each problem is returned
+/// with the byte offsets of the part of `source` it refers to.
+/// Non-finite coordinates (such as `NaN` or `inf`) are accepted by the parser
+/// so that they can be reported.
+///
+/// ```rust
+/// use geo_validity_check::{validate_wkt, WktValidationError};
+///
+/// let source = "POLYGON((0 0, 1 0, 1 NaN, 0 1, 0 0))";
+/// match validate_wkt(source) {
+///     Err(WktValidationError::Invalid(problems)) => {
+///         let span = problems[0].1.clone().unwrap();
+///         assert_eq!(&source[span], "NaN");
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+pub fn validate_wkt(source: &str) -> Result<Geometry, WktValidationError> {
+    validate_wkt_with(source, &ValidationConfig::default())
+}
+
+/// Parse a WKT string and check the validity of the resulting geometry,
+/// performing the checks enabled in `config` (see `validate_wkt`).
+pub fn validate_wkt_with(
+    source: &str,
+    config: &ValidationConfig,
+) -> Result<Geometry, WktValidationError> {
+    let scanned = scan(source);
+    let wkt = ::wkt::Wkt::<f64>::from_str(&scanned.prepared)
+        .map_err(|e| WktValidationError::Parse(e.to_string()))?;
+    let geometry = Geometry::try_from(wkt).map_err(|e| WktValidationError::Parse(e.to_string()))?;
+
+    match geometry.explain_invalidity_with(config) {
+        None => Ok(geometry),
+        Some(report) => Err(WktValidationError::Invalid(
+            report
+                .0
+                .into_iter()
+                .map(|problem| {
+                    let span = scanned
+                        .root
+                        .as_ref()
+                        .and_then(|root| span_of(source, root, &problem));
+                    ProblemInSource(problem, span)
+                })
+                .collect(),
+        )),
+    }
+}
+
+/// The structure of a WKT string, as a tree of parenthesized lists
+/// whose leaves are coordinates.
+enum Node {
+    /// The spans of the numbers of a coordinate
+    Coord(Vec<Range<usize>>),
+    /// A parenthesized list (or an `EMPTY` token) and its content
+    List(Range<usize>, Vec<Node>),
+}
+
+impl Node {
+    fn span(&self) -> Range<usize> {
+        match self {
+            Node::Coord(numbers) => numbers[0].start..numbers[numbers.len() - 1].end,
+            Node::List(span, _) => span.clone(),
+        }
+    }
+
+    fn child(&self, i: usize) -> Option<&Node> {
+        match self {
+            Node::Coord(_) => None,
+            Node::List(_, children) => children.get(i),
+        }
+    }
+
+    fn coord(&self, position: &CoordinatePosition) -> Option<&Node> {
+        match position.0 {
+            None => Some(self),
+            Some(i) => self.child(i),
+        }
+    }
+}
+
+struct Scanned {
+    /// The first geometry of the source text
+    root: Option<Node>,
+    /// The source text, with a '+' sign added before the unsigned non-finite numbers
+    /// (that the WKT parser doesn't recognize as numbers)
+    prepared: String,
+}
+
+fn is_non_finite_word(token: &str) -> bool {
+    ["nan", "inf", "infinity"]
+        .iter()
+        .any(|w| token.eq_ignore_ascii_case(w))
+}
+
+fn is_number(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-' || c == '+')
+        || is_non_finite_word(token)
+}
+
+/// Scan the source text to record the span of each coordinate.
+fn scan(source: &str) -> Scanned {
+    let mut prepared = String::with_capacity(source.len());
+    // The lists being read (with their start offset) and the coordinate being read
+    let mut stack: Vec<(usize, Vec<Node>)> = vec![(0, Vec::new())];
+    let mut coord: Vec<Range<usize>> = Vec::new();
+
+    fn flush(coord: &mut Vec<Range<usize>>, stack: &mut [(usize, Vec<Node>)]) {
+        if !coord.is_empty() {
+            if let Some((_, children)) = stack.last_mut() {
+                children.push(Node::Coord(std::mem::take(coord)));
+            }
+        }
+    }
+
+    let mut chars = source.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '(' => {
+                flush(&mut coord, &mut stack);
+                stack.push((i, Vec::new()));
+            }
+            ')' => {
+                flush(&mut coord, &mut stack);
+                if stack.len() > 1 {
+                    let (start, children) = stack.pop().unwrap();
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .1
+                        .push(Node::List(start..i + 1, children));
+                }
+            }
+            ',' => flush(&mut coord, &mut stack),
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, c)) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == ',' {
+                        break;
+                    }
+                    end = j + c.len_utf8();
+                    chars.next();
+                }
+                let token = &source[i..end];
+                if is_number(token) {
+                    if is_non_finite_word(token) {
+                        prepared.push('+');
+                    }
+                    coord.push(i..end);
+                } else if token.eq_ignore_ascii_case("EMPTY") {
+                    flush(&mut coord, &mut stack);
+                    if let Some((_, children)) = stack.last_mut() {
+                        children.push(Node::List(i..end, Vec::new()));
+                    }
+                }
+                // Other words (geometry types, dimension tags) are ignored
+                prepared.push_str(token);
+                continue;
+            }
+        }
+        prepared.push(c);
+    }
+
+    Scanned {
+        root: stack.swap_remove(0).1.into_iter().next(),
+        prepared,
+    }
+}
+
+fn ring<'a>(polygon: &'a Node, role: &RingRole) -> Option<&'a Node> {
+    match role {
+        RingRole::Exterior => polygon.child(0),
+        RingRole::Interior(i) => polygon.child(i + 1),
+    }
+}
+
+/// Find the span of the source text corresponding to the position of a problem.
+fn span_of(source: &str, root: &Node, problem: &ProblemAtPosition) -> Option<Range<usize>> {
+    let node = node_at(root, &problem.1)?;
+    match (node, &problem.0) {
+        // Point at the offending number of the coordinate
+        (Node::Coord(numbers), Problem::NotFinite) => numbers
+            .iter()
+            .find(|span| {
+                f64::from_str(&source[(*span).clone()])
+                    .map(|n| !n.is_finite())
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .or_else(|| Some(node.span())),
+        _ => Some(node.span()),
+    }
+}
+
+/// Find the node of the source text corresponding to a position in the geometry.
+fn node_at<'a>(root: &'a Node, position: &ProblemPosition) -> Option<&'a Node> {
+    match position {
+        ProblemPosition::Point => root.child(0),
+        ProblemPosition::Whole => Some(root),
+        ProblemPosition::LineString(coord) => root.coord(coord),
+        ProblemPosition::MultiPoint(geom) => root.child(geom.0),
+        ProblemPosition::MultiLineString(geom, coord) => root.child(geom.0)?.coord(coord),
+        ProblemPosition::Polygon(role, coord) => ring(root, role)?.coord(coord),
+        ProblemPosition::MultiPolygon(geom, role, coord) => {
+            ring(root.child(geom.0)?, role)?.coord(coord)
+        }
+        ProblemPosition::GeometryCollection(geom, position) => {
+            node_at(root.child(geom.0)?, position)
+        }
+        // These geometries have no WKT representation
+        ProblemPosition::Line(_) | ProblemPosition::Triangle(_) | ProblemPosition::Rect(_) => {
+            Some(root)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        validate_wkt, CoordinatePosition, GeometryPosition, Problem, ProblemAtPosition,
+        ProblemInSource, ProblemPosition, RingRole, WktValidationError,
+    };
+
+    #[test]
+    fn test_wkt_valid() {
+        let geom = validate_wkt("POLYGON((0 0, 1 0, 1 1, 0 1, 0 0))");
+        assert!(matches!(geom, Ok(geo_types::Geometry::Polygon(_))));
+    }
+
+    #[test]
+    fn test_wkt_bowtie() {
+        let source = "POLYGON((0 0, 2 2, 2 0, 0 2, 0 0))";
+        assert_eq!(
+            validate_wkt(source),
+            Err(WktValidationError::Invalid(vec![ProblemInSource(
+                ProblemAtPosition(
+                    Problem::SelfIntersection,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(0)))
+                ),
+                Some(9..12)
+            )]))
+        );
+        assert_eq!(&source[9..12], "0 0");
+    }
+
+    #[test]
+    fn test_wkt_parse_error() {
+        assert!(matches!(
+            validate_wkt("POLYGON((0 0, 1 0, 1 1"),
+            Err(WktValidationError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_wkt_polygon_nan_offset() {
+        let source = "POLYGON((0 0, 1 0, 1 NaN, 0 1, 0 0))";
+        let problems = match validate_wkt(source) {
+            Err(WktValidationError::Invalid(problems)) => problems,
+            _ => panic!("expected an invalid geometry"),
+        };
+        let not_finite = problems
+            .iter()
+            .find(|p| p.0 .0 == Problem::NotFinite)
+            .unwrap();
+        assert_eq!(
+            not_finite,
+            &ProblemInSource(
+                ProblemAtPosition(
+                    Problem::NotFinite,
+                    ProblemPosition::Polygon(RingRole::Exterior, CoordinatePosition(Some(2)))
+                ),
+                Some(21..24)
+            )
+        );
+        assert_eq!(&source[21..24], "NaN");
+    }
+
+    #[test]
+    fn test_wkt_geometrycollection_offset() {
+        // The second LineString of the MultiLineString has too few points
+        let source = "GEOMETRYCOLLECTION(POINT(1 2), MULTILINESTRING((0 0, 1 1), (2 2, 2 2)))";
+        assert_eq!(
+            validate_wkt(source),
+            Err(WktValidationError::Invalid(vec![ProblemInSource(
+                ProblemAtPosition(
+                    Problem::TooFewPoints,
+                    ProblemPosition::GeometryCollection(
+                        GeometryPosition(1),
+                        Box::new(ProblemPosition::MultiLineString(
+                            GeometryPosition(1),
+                            CoordinatePosition(Some(0))
+                        ))
+                    )
+                ),
+                Some(60..63)
+            )]))
+        );
+        assert_eq!(&source[60..63], "2 2");
+    }
+}